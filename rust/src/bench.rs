@@ -0,0 +1,286 @@
+use std::collections::BTreeMap;
+use std::fmt::{Display, Formatter};
+use std::path::Path;
+use std::time::{Duration, Instant};
+use std::{fs, io};
+
+use crate::core::AocError;
+use crate::solution::{DynSolution, Part};
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Hash, Ord, PartialOrd)]
+pub enum Phase {
+    Parse,
+    Part(Part),
+}
+
+impl Display for Phase {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Phase::Parse => f.pad("parse"),
+            Phase::Part(part) => f.pad(&format!("part-{}", part)),
+        }
+    }
+}
+
+impl TryFrom<&str> for Phase {
+    type Error = AocError;
+
+    fn try_from(value: &str) -> Result<Self, Self::Error> {
+        match value {
+            "parse" => Ok(Phase::Parse),
+            _ => value
+                .strip_prefix("part-")
+                .and_then(|part| part.parse().ok())
+                .map(Phase::Part)
+                .ok_or_else(|| AocError::new(format!("Unknown phase {:?}", value))),
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct Stats {
+    pub min: Duration,
+    pub median: Duration,
+    pub max: Duration,
+}
+
+impl Stats {
+    /// Summarises samples; `None` when there are none. For an even count the median is the
+    /// upper of the two middle samples.
+    pub fn from_samples(samples: &[Duration]) -> Option<Self> {
+        let mut sorted = samples.to_vec();
+        sorted.sort();
+
+        Some(Self {
+            min: *sorted.first()?,
+            median: sorted[sorted.len() / 2],
+            max: *sorted.last()?,
+        })
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct Measurement {
+    pub day: u8,
+    pub phase: Phase,
+    pub stats: Result<Stats, AocError>,
+}
+
+/// Times parsing and both parts of a solution `iterations` times, after one untimed warm-up
+/// run. A phase that fails is reported once and not retried; when parsing fails, the parts
+/// are not measured at all.
+pub fn bench_solution(
+    solution: &dyn DynSolution,
+    input: &str,
+    iterations: usize,
+) -> Vec<Measurement> {
+    let day = solution.day();
+    let measurement =
+        |phase: Phase, stats: Result<Stats, AocError>| Measurement { day, phase, stats };
+
+    let parsed = match solution.parse(input) {
+        Ok(parsed) => parsed,
+        Err(err) => return vec![measurement(Phase::Parse, Err(err))],
+    };
+
+    let mut measurements = vec![measurement(
+        Phase::Parse,
+        time(iterations, || solution.parse(input).map(|_| ())),
+    )];
+
+    for part in Part::ALL {
+        let stats = solution
+            .run(&parsed, part)
+            .and_then(|_| time(iterations, || solution.run(&parsed, part).map(|_| ())));
+        measurements.push(measurement(Phase::Part(part), stats));
+    }
+
+    measurements
+}
+
+fn time<F>(iterations: usize, mut run: F) -> Result<Stats, AocError>
+where
+    F: FnMut() -> Result<(), AocError>,
+{
+    let mut samples = Vec::with_capacity(iterations);
+    for _ in 0..iterations {
+        let start = Instant::now();
+        run()?;
+        samples.push(start.elapsed());
+    }
+
+    Stats::from_samples(&samples).ok_or_else(|| AocError::new("Need at least one iteration"))
+}
+
+/// Previously saved stats, one `day phase min median max` line per measurement, in nanoseconds.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct Baseline {
+    stats: BTreeMap<(u8, Phase), Stats>,
+}
+
+impl Baseline {
+    pub fn parse(input: &str) -> Result<Self, AocError> {
+        let mut stats = BTreeMap::new();
+
+        for (idx, line) in input.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+
+            let fields: Vec<&str> = line.split_whitespace().collect();
+            let invalid = || {
+                AocError::at("Expected `day phase min median max`", line, 1, line).on_line(idx + 1)
+            };
+            let nanos = |field: &str| {
+                field
+                    .parse()
+                    .map(Duration::from_nanos)
+                    .map_err(|_| invalid())
+            };
+
+            match fields.as_slice() {
+                [day, phase, min, median, max] => {
+                    stats.insert(
+                        (
+                            day.parse().map_err(|_| invalid())?,
+                            Phase::try_from(*phase).map_err(|_| invalid())?,
+                        ),
+                        Stats {
+                            min: nanos(min)?,
+                            median: nanos(median)?,
+                            max: nanos(max)?,
+                        },
+                    );
+                }
+                _ => return Err(invalid()),
+            }
+        }
+
+        Ok(Self { stats })
+    }
+
+    pub fn load(path: &Path) -> Result<Self, AocError> {
+        Baseline::parse(&fs::read_to_string(path)?)
+    }
+
+    pub fn from_measurements(measurements: &[Measurement]) -> Self {
+        Self {
+            stats: measurements
+                .iter()
+                .filter_map(|m| Some(((m.day, m.phase), m.stats.clone().ok()?)))
+                .collect(),
+        }
+    }
+
+    pub fn get(&self, day: u8, phase: Phase) -> Option<&Stats> {
+        self.stats.get(&(day, phase))
+    }
+
+    pub fn save(&self, path: &Path) -> io::Result<()> {
+        fs::write(path, self.to_string())
+    }
+}
+
+impl Display for Baseline {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        writeln!(f, "# day phase min_ns median_ns max_ns")?;
+        for ((day, phase), stats) in &self.stats {
+            writeln!(
+                f,
+                "{} {} {} {} {}",
+                day,
+                phase,
+                stats.min.as_nanos(),
+                stats.median.as_nanos(),
+                stats.max.as_nanos()
+            )?;
+        }
+
+        Ok(())
+    }
+}
+
+/// The relative change of `current` against `baseline`, e.g. `0.25` for 25% slower.
+pub fn change(baseline: &Stats, current: &Stats) -> f64 {
+    let baseline = baseline.median.as_secs_f64();
+    if baseline == 0.0 {
+        return 0.0;
+    }
+
+    current.median.as_secs_f64() / baseline - 1.0
+}
+
+/// How much a median has to slow down by before it counts, however large the relative change.
+/// Sub-microsecond phases jitter by tens of percent from one run to the next.
+pub const NOISE_FLOOR: Duration = Duration::from_micros(1);
+
+/// Whether a median slowed down by more than `threshold` (a fraction, `0.1` for 10%) and by
+/// more than the [`NOISE_FLOOR`].
+pub fn is_regression(baseline: &Stats, current: &Stats, threshold: f64) -> bool {
+    current.median.saturating_sub(baseline.median) > NOISE_FLOOR
+        && change(baseline, current) > threshold
+}
+
+#[cfg(test)]
+mod bench_tests {
+    use crate::bench::*;
+    use crate::days::day_6::Day6;
+
+    fn millis(values: &[u64]) -> Vec<Duration> {
+        values.iter().copied().map(Duration::from_millis).collect()
+    }
+
+    #[test]
+    fn stats_from_samples() {
+        let stats = Stats::from_samples(&millis(&[5, 1, 3, 9, 4])).expect("Expected stats");
+
+        assert_eq!(stats.min, Duration::from_millis(1));
+        assert_eq!(stats.median, Duration::from_millis(4));
+        assert_eq!(stats.max, Duration::from_millis(9));
+        assert_eq!(Stats::from_samples(&[]), None);
+    }
+
+    #[test]
+    fn baseline_round_trip() {
+        let measurements = bench_solution(&Day6, "mjqjpqmgbljsphdztnvjfqwrcgsmlb", 3);
+        let baseline = Baseline::from_measurements(&measurements);
+
+        assert_eq!(measurements.len(), 3);
+        assert!(baseline.get(6, Phase::Part(Part::Two)).is_some());
+        assert_eq!(Baseline::parse(&baseline.to_string()), Ok(baseline));
+    }
+
+    #[test]
+    fn baseline_errors_are_located() {
+        let err = Baseline::parse("# header\n6 parse 1 2 3\n6 part-3 1 2 3")
+            .expect_err("Expected an unknown phase");
+
+        assert_eq!(err.location.map(|location| location.line), Some(3));
+    }
+
+    #[test]
+    fn regressions_past_threshold() {
+        let stats = |median| Stats {
+            min: Duration::from_millis(median),
+            median: Duration::from_millis(median),
+            max: Duration::from_millis(median),
+        };
+
+        assert!(is_regression(&stats(100), &stats(125), 0.2));
+        assert!(!is_regression(&stats(100), &stats(115), 0.2));
+        assert!(!is_regression(&stats(100), &stats(50), 0.2));
+    }
+
+    #[test]
+    fn jitter_is_not_a_regression() {
+        let stats = |median| Stats {
+            min: Duration::from_nanos(median),
+            median: Duration::from_nanos(median),
+            max: Duration::from_nanos(median),
+        };
+
+        assert!(!is_regression(&stats(250), &stats(310), 0.2));
+        assert!(is_regression(&stats(2_500), &stats(3_600), 0.2));
+    }
+}
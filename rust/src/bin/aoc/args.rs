@@ -0,0 +1,94 @@
+use std::collections::HashMap;
+use std::str::FromStr;
+
+/// Command line arguments split into positionals and `--flag value` options.
+#[derive(Debug, Default)]
+pub struct Args {
+    positionals: Vec<String>,
+    options: HashMap<String, Vec<String>>,
+}
+
+impl Args {
+    pub fn parse<I: IntoIterator<Item = String>>(args: I) -> Result<Self, String> {
+        let mut parsed = Args::default();
+        let mut args = args.into_iter().peekable();
+
+        while let Some(arg) = args.next() {
+            match arg.strip_prefix("--") {
+                Some("") => return Err("Unexpected bare `--`".to_string()),
+                Some(name) => {
+                    let value = match args.peek() {
+                        Some(next) if !next.starts_with("--") => args.next().unwrap_or_default(),
+                        _ => "true".to_string(),
+                    };
+                    parsed
+                        .options
+                        .entry(name.to_string())
+                        .or_default()
+                        .push(value);
+                }
+                None => parsed.positionals.push(arg),
+            }
+        }
+
+        Ok(parsed)
+    }
+
+    pub fn positional(&self, idx: usize) -> Option<&str> {
+        self.positionals.get(idx).map(String::as_str)
+    }
+
+    pub fn option(&self, name: &str) -> Option<&str> {
+        self.options
+            .get(name)
+            .and_then(|values| values.last())
+            .map(String::as_str)
+    }
+
+    /// Every value given for a repeatable option, in order.
+    pub fn options(&self, name: &str) -> &[String] {
+        self.options.get(name).map_or(&[], Vec::as_slice)
+    }
+
+    pub fn parse_option<T: FromStr>(&self, name: &str) -> Result<Option<T>, String> {
+        self.option(name)
+            .map(|value| {
+                value
+                    .parse()
+                    .map_err(|_| format!("Invalid value for --{}: {}", name, value))
+            })
+            .transpose()
+    }
+}
+
+#[cfg(test)]
+mod args_tests {
+    use crate::args::Args;
+
+    fn parse(input: &str) -> Args {
+        Args::parse(input.split_whitespace().map(String::from)).expect("Could not parse args")
+    }
+
+    #[test]
+    fn positionals_and_options() {
+        let args = parse("run 4 --part 2 --input - --verbose");
+
+        assert_eq!(args.positional(0), Some("run"));
+        assert_eq!(args.positional(1), Some("4"));
+        assert_eq!(args.positional(2), None);
+        assert_eq!(args.option("part"), Some("2"));
+        assert_eq!(args.option("input"), Some("-"));
+        assert_eq!(args.option("verbose"), Some("true"));
+        assert_eq!(args.parse_option::<u8>("part"), Ok(Some(2)));
+        assert!(args.parse_option::<u8>("input").is_err());
+    }
+
+    #[test]
+    fn repeated_options() {
+        let args = parse("run 3 --opt compartments=3 --opt group-size=2");
+
+        assert_eq!(args.options("opt"), ["compartments=3", "group-size=2"]);
+        assert_eq!(args.option("opt"), Some("group-size=2"));
+        assert!(args.options("part").is_empty());
+    }
+}
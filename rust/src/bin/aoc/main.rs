@@ -0,0 +1,482 @@
+mod args;
+
+use advent_of_code_2022::bench::{bench_solution, change, is_regression, Baseline, Measurement};
+use advent_of_code_2022::core::InputSource;
+use advent_of_code_2022::days::day_3::{Day3, Report};
+use advent_of_code_2022::days::day_4::{section_index, Day4, Diagram, Segment, SegmentGroup};
+use advent_of_code_2022::days::registry;
+use advent_of_code_2022::examples::run_day_examples;
+use advent_of_code_2022::scaffold::scaffold_day;
+use advent_of_code_2022::solution::{day_name, DynSolution, Part, Registry};
+use advent_of_code_2022::verify::{
+    default_answers_path, read_day_input, verify, AnswerKey, Status,
+};
+use args::Args;
+use std::path::{Path, PathBuf};
+use std::process::ExitCode;
+use std::time::Duration;
+
+const USAGE: &str = "Usage:
+    aoc run <day> [--part 1|2|both] [--input <path>|-] [--opt <key>=<value>]...
+    aoc bench [<day>] [--iterations <n>] [--save <path>] [--baseline <path>] [--threshold <percent>]
+    aoc verify [--answers <path>]
+    aoc examples [<day>]
+    aoc rucksacks [--part 1|2|both] [--input <path>|-] [--opt <key>=<value>]... [--format table|json] [--validate|--discover]
+    aoc sections <section|start-end>... [--input <path>|-] [--opt <key>=<value>]...
+    aoc draw [--lines <first>-<last>] [--span <start>-<end>] [--width <n>] [--input <path>|-] [--opt <key>=<value>]...
+    aoc new <day> [--root <crate dir>]
+    aoc list";
+
+fn input_source(args: &Args, day: u8) -> InputSource {
+    match args.option("input") {
+        Some("-") => InputSource::Stdin,
+        Some(path) => InputSource::Path(PathBuf::from(path)),
+        None => InputSource::for_day(&day_name(day)),
+    }
+}
+
+fn parts(args: &Args) -> Result<Vec<Part>, String> {
+    match args.option("part") {
+        None | Some("both") => Ok(Part::ALL.to_vec()),
+        Some(part) => Ok(vec![part.parse()?]),
+    }
+}
+
+fn read_input(args: &Args, day: u8) -> Result<String, String> {
+    input_source(args, day)
+        .read_to_string()
+        .map_err(|err| err.to_string())
+}
+
+fn parse_day(value: &str, registry: &Registry) -> Result<u8, String> {
+    let day = value
+        .parse()
+        .map_err(|_| format!("Invalid day: {}", value))?;
+
+    match registry.get(day) {
+        Some(_) => Ok(day),
+        None => Err(format!("Day {} is not implemented", day)),
+    }
+}
+
+fn day(args: &Args, registry: &Registry) -> Result<u8, String> {
+    parse_day(args.positional(1).ok_or("Missing <day>")?, registry)
+}
+
+/// The day named on the command line, or every registered day when none is.
+fn days(args: &Args, registry: &Registry) -> Result<Vec<u8>, String> {
+    match args.positional(1) {
+        Some(day) => Ok(vec![parse_day(day, registry)?]),
+        None => Ok(registry.days().collect()),
+    }
+}
+
+/// Hands every `--opt key=value` to the solution.
+fn configure(args: &Args, solution: &mut dyn DynSolution) -> Result<(), String> {
+    for option in args.options("opt") {
+        let (key, value) = option
+            .split_once('=')
+            .ok_or_else(|| format!("Expected --opt <key>=<value>, found {}", option))?;
+        solution
+            .configure(key, value)
+            .map_err(|err| err.to_string())?;
+    }
+
+    Ok(())
+}
+
+fn run(args: &Args) -> Result<(), String> {
+    let mut registry = registry();
+    let day = day(args, &registry)?;
+    let parts = parts(args)?;
+    let solution = registry.get_mut(day).ok_or("Unknown day")?;
+    configure(args, solution)?;
+
+    let mut reader = input_source(args, day)
+        .open()
+        .map_err(|err| err.to_string())?;
+    let parsed = solution
+        .parse_reader(&mut reader)
+        .map_err(|err| err.to_string())?;
+
+    let mut failed = 0;
+    for part in parts {
+        match solution.run(&parsed, part) {
+            Ok(answer) => println!("Day {} Part {}: {}", day, part, answer),
+            Err(err) => {
+                eprintln!("Day {} Part {}: {}", day, part, err);
+                failed += 1;
+            }
+        }
+    }
+
+    match failed {
+        0 => Ok(()),
+        _ => Err(format!("{} part(s) failed", failed)),
+    }
+}
+
+fn format_duration(duration: Duration) -> String {
+    format!("{:.1?}", duration)
+}
+
+fn print_measurement(
+    measurement: &Measurement,
+    baseline: Option<&Baseline>,
+    threshold: f64,
+) -> bool {
+    let stats = match &measurement.stats {
+        Ok(stats) => stats,
+        Err(err) => {
+            println!("{:>3}  {:<7} {}", measurement.day, measurement.phase, err);
+            return false;
+        }
+    };
+
+    let comparison = baseline.and_then(|baseline| baseline.get(measurement.day, measurement.phase));
+    let regressed = comparison.is_some_and(|previous| is_regression(previous, stats, threshold));
+
+    let row = format!(
+        "{:>3}  {:<7} {:>10} {:>10} {:>10} {:>10} {:>8} {}",
+        measurement.day,
+        measurement.phase,
+        format_duration(stats.min),
+        format_duration(stats.median),
+        format_duration(stats.max),
+        comparison.map_or("-".to_string(), |previous| format_duration(previous.median)),
+        comparison.map_or("-".to_string(), |previous| format!(
+            "{:+.1}%",
+            change(previous, stats) * 100.0
+        )),
+        if regressed { "REGRESSION" } else { "" }
+    );
+    println!("{}", row.trim_end());
+
+    regressed
+}
+
+fn bench(args: &Args) -> Result<(), String> {
+    let registry = registry();
+    let iterations = args.parse_option("iterations")?.unwrap_or(10);
+    let threshold = args.parse_option::<f64>("threshold")?.unwrap_or(10.0) / 100.0;
+    let baseline = args
+        .option("baseline")
+        .map(|path| Baseline::load(Path::new(path)).map_err(|err| err.to_string()))
+        .transpose()?;
+
+    println!(
+        "{:>3}  {:<7} {:>10} {:>10} {:>10} {:>10} {:>8}",
+        "day", "phase", "min", "median", "max", "baseline", "change"
+    );
+
+    let mut measurements = vec![];
+    let mut regressions = 0;
+    for day in days(args, &registry)? {
+        let solution = registry.get(day).ok_or("Unknown day")?;
+        for measurement in bench_solution(solution, &read_input(args, day)?, iterations) {
+            if print_measurement(&measurement, baseline.as_ref(), threshold) {
+                regressions += 1;
+            }
+            measurements.push(measurement);
+        }
+    }
+
+    if let Some(path) = args.option("save") {
+        Baseline::from_measurements(&measurements)
+            .save(Path::new(path))
+            .map_err(|err| format!("Couldn't save baseline to {}: {}", path, err))?;
+    }
+
+    match regressions {
+        0 => Ok(()),
+        _ => Err(format!("{} phase(s) regressed", regressions)),
+    }
+}
+
+fn verify_answers(args: &Args) -> Result<(), String> {
+    let path = args
+        .option("answers")
+        .map_or_else(default_answers_path, PathBuf::from);
+    let key = AnswerKey::load(&path).map_err(|err| err.to_string())?;
+
+    println!(
+        "{:>3}  {:>4}  {:<10} {:<8} {:<20} actual",
+        "day", "part", "input", "status", "expected"
+    );
+
+    let mut failed = 0;
+    for check in verify(&registry(), &key, read_day_input) {
+        let status = check.status();
+        let actual = match &check.actual {
+            Ok(answer) => answer.clone(),
+            Err(err) => err
+                .to_string()
+                .lines()
+                .next()
+                .unwrap_or_default()
+                .to_string(),
+        };
+        if matches!(status, Status::Fail | Status::Error) {
+            failed += 1;
+        }
+
+        println!(
+            "{:>3}  {:>4}  {:<10} {:<8} {:<20} {}",
+            check.day,
+            check.part,
+            check.input,
+            status,
+            check.expected.as_deref().unwrap_or("-"),
+            actual
+        );
+    }
+
+    match failed {
+        0 => Ok(()),
+        _ => Err(format!("{} answer(s) did not verify", failed)),
+    }
+}
+
+fn examples(args: &Args) -> Result<(), String> {
+    let registry = registry();
+    let mut failed = 0;
+
+    for day in days(args, &registry)? {
+        let solution = registry.get(day).ok_or("Unknown day")?;
+        let outcomes = run_day_examples(solution).map_err(|err| err.to_string())?;
+        let passed = outcomes.iter().filter(|outcome| outcome.passed()).count();
+
+        println!("Day {}: {}/{} examples passed", day, passed, outcomes.len());
+        for outcome in outcomes.iter().filter(|outcome| !outcome.passed()) {
+            println!("{}", outcome);
+            failed += 1;
+        }
+    }
+
+    match failed {
+        0 => Ok(()),
+        _ => Err(format!("{} example(s) failed", failed)),
+    }
+}
+
+fn print_report_row(report: &Report) {
+    let lines = match report.lines {
+        (first, last) if first == last => first.to_string(),
+        (first, last) => format!("{}-{}", first, last),
+    };
+    let common: Vec<String> = report
+        .common
+        .iter()
+        .map(|(item, priority)| match priority {
+            Some(priority) => format!("{}={}", item, priority),
+            None => format!("{}=?", item),
+        })
+        .collect();
+
+    let row = format!(
+        "{:>4}  {:<9} {:<12} {:>5} {:<2} {}",
+        report.part,
+        lines,
+        common.join(" "),
+        report.score,
+        if report.is_unexpected() { "!" } else { "" },
+        report.members.join(" ")
+    );
+    println!("{}", row.trim_end());
+}
+
+/// Shows how day 3 scored every rucksack (part 1) and every group (part 2), flagging the ones
+/// without exactly one common item. With `--validate` it lists what's wrong with the input
+/// instead, and with `--discover` it groups shuffled rucksacks by badge.
+fn rucksacks(args: &Args) -> Result<(), String> {
+    let mut day = Day3::default();
+    configure(args, &mut day)?;
+    if args.option("validate").is_some() {
+        let violations = day
+            .validate(read_input(args, 3)?.as_str())
+            .map_err(|err| err.to_string())?;
+        for violation in &violations {
+            println!("{}", violation);
+        }
+
+        return match violations.len() {
+            0 => Ok(()),
+            count => Err(format!("{} problem(s) with the input", count)),
+        };
+    }
+    if args.option("discover").is_some() {
+        let groups = day
+            .discover_groups(read_input(args, 3)?.as_str())
+            .map_err(|err| err.to_string())?
+            .ok_or_else(|| {
+                format!(
+                    "No grouping into groups of {} sharing exactly one badge",
+                    day.group_size
+                )
+            })?;
+        for group in groups {
+            let lines: Vec<String> = group.members.iter().map(ToString::to_string).collect();
+            println!(
+                "{} ({}): lines {}",
+                group.badge,
+                day.scheme.priority(group.badge).unwrap_or_default(),
+                lines.join(", ")
+            );
+        }
+
+        return Ok(());
+    }
+
+    let json = match args.option("format") {
+        None | Some("table") => false,
+        Some("json") => true,
+        Some(format) => return Err(format!("Unknown format {}, expected table or json", format)),
+    };
+    let input = read_input(args, 3)?;
+
+    if !json {
+        println!(
+            "{:>4}  {:<9} {:<12} {:>5} {:<2} members",
+            "part", "lines", "common", "score", ""
+        );
+    }
+
+    let mut unexpected = 0;
+    for part in parts(args)? {
+        let reports = match part {
+            Part::One => day.explain_rucksacks(input.as_str()),
+            Part::Two => day.explain_groups(input.as_str()),
+        }
+        .map_err(|err| err.to_string())?;
+
+        for report in &reports {
+            match json {
+                true => println!("{}", report.to_json()),
+                false => print_report_row(report),
+            }
+        }
+        unexpected += reports
+            .iter()
+            .filter(|report| report.is_unexpected())
+            .count();
+    }
+
+    if unexpected > 0 {
+        eprintln!("{} report(s) without exactly one common item", unexpected);
+    }
+
+    Ok(())
+}
+
+/// The day 4 groups, parsed with any `--opt` given.
+fn segment_groups(args: &Args) -> Result<Vec<SegmentGroup>, String> {
+    let mut day = Day4::default();
+    configure(args, &mut day)?;
+
+    day.parse_groups(read_input(args, 4)?.as_str())
+        .map_err(|err| err.to_string())
+}
+
+/// Lists the day 4 elves cleaning each section, or any section of each range, given.
+fn sections(args: &Args) -> Result<(), String> {
+    let queries: Vec<&str> = (1..).map_while(|idx| args.positional(idx)).collect();
+    if queries.is_empty() {
+        return Err("Missing <section|start-end>".to_string());
+    }
+
+    let groups = segment_groups(args)?;
+    let index = section_index(&groups);
+
+    for query in queries {
+        let found = match query.contains('-') {
+            true => index.overlapping(&Segment::try_from(query).map_err(|err| err.to_string())?),
+            false => index.stab(
+                query
+                    .parse()
+                    .map_err(|_| format!("Invalid section: {}", query))?,
+            ),
+        };
+
+        println!("{}: {} elf(s)", query, found.len());
+        for (segment, elf) in found {
+            println!("    {:<16} {}", elf, segment);
+        }
+    }
+
+    Ok(())
+}
+
+/// A `start-end` option, such as `--span 1-9`.
+fn segment_option(args: &Args, name: &str) -> Result<Option<Segment>, String> {
+    args.option(name)
+        .map(|value| Segment::try_from(value).map_err(|err| err.to_string()))
+        .transpose()
+}
+
+/// Draws the day 4 assignments, or the lines given by `--lines`, as rows of sections.
+fn draw(args: &Args) -> Result<(), String> {
+    let groups = segment_groups(args)?;
+    let lines =
+        segment_option(args, "lines")?.unwrap_or_else(|| Segment::new(1, groups.len() as u32));
+    let first = (lines.start().max(1) as usize - 1).min(groups.len());
+    let last = (lines.end() as usize).clamp(first, groups.len());
+
+    let diagram = Diagram {
+        span: segment_option(args, "span")?,
+        max_width: args
+            .parse_option("width")?
+            .unwrap_or(Diagram::default().max_width),
+    };
+    print!("{}", diagram.render(&groups[first..last]));
+
+    Ok(())
+}
+
+fn new_day(args: &Args) -> Result<(), String> {
+    let day = args
+        .positional(1)
+        .ok_or("Missing <day>")?
+        .parse()
+        .map_err(|_| format!("Invalid day: {}", args.positional(1).unwrap_or_default()))?;
+    let root = args
+        .option("root")
+        .map_or_else(|| PathBuf::from(env!("CARGO_MANIFEST_DIR")), PathBuf::from);
+
+    for path in scaffold_day(&root, day).map_err(|err| err.to_string())? {
+        println!("wrote {}", path.display());
+    }
+
+    Ok(())
+}
+
+fn list() -> Result<(), String> {
+    for day in registry().days() {
+        println!("{}", day);
+    }
+
+    Ok(())
+}
+
+fn main() -> ExitCode {
+    let result = Args::parse(std::env::args().skip(1)).and_then(|args| match args.positional(0) {
+        Some("run") => run(&args),
+        Some("bench") => bench(&args),
+        Some("verify") => verify_answers(&args),
+        Some("examples") => examples(&args),
+        Some("rucksacks") => rucksacks(&args),
+        Some("sections") => sections(&args),
+        Some("draw") => draw(&args),
+        Some("new") => new_day(&args),
+        Some("list") => list(),
+        Some(command) => Err(format!("Unknown command {}\n{}", command, USAGE)),
+        None => Err(USAGE.to_string()),
+    });
+
+    match result {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(err) => {
+            eprintln!("{}", err);
+            ExitCode::FAILURE
+        }
+    }
+}
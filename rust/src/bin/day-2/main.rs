@@ -0,0 +1,7 @@
+use advent_of_code_2022::days::day_2::Day2;
+use advent_of_code_2022::solution::run_main;
+use std::process::ExitCode;
+
+fn main() -> ExitCode {
+    run_main(&Day2)
+}
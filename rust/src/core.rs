@@ -1,15 +1,381 @@
-use std::{env, fs};
+use std::fmt::{Display, Formatter};
+use std::io::{BufRead, BufReader, Read};
+use std::path::{Path, PathBuf};
+use std::{env, fs, io};
 
-pub fn get_data(day: &str) -> std::io::Result<String> {
-    let path = format!(
-        "{}/src/bin/{}/input.txt",
-        env::current_dir()?.display(),
-        day
-    );
+/// Where in the input an error happened. `line` and `column` are 1-based, `column` counts
+/// characters, and `source` is the whole input line `text` was found in.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Location {
+    pub line: usize,
+    pub column: usize,
+    pub text: String,
+    pub source: String,
+}
+
+/// The error every parser and solver reports, optionally pointing at the offending input.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct AocError {
+    pub message: String,
+    pub location: Option<Location>,
+}
+
+impl AocError {
+    pub fn new(message: impl Into<String>) -> Self {
+        Self {
+            message: message.into(),
+            location: None,
+        }
+    }
+
+    /// An error about `text`, found at `column` of the single line `source`.
+    pub fn at(message: impl Into<String>, source: &str, column: usize, text: &str) -> Self {
+        Self {
+            message: message.into(),
+            location: Some(Location {
+                line: 1,
+                column,
+                text: text.to_string(),
+                source: source.to_string(),
+            }),
+        }
+    }
+
+    /// Moves the location to `line`, for parsers that only see one line at a time.
+    pub fn on_line(mut self, line: usize) -> Self {
+        if let Some(location) = self.location.as_mut() {
+            location.line = line;
+        }
+        self
+    }
+
+    /// Moves the location down by `lines`, for parsers that see a later section of the input.
+    pub fn offset_lines(mut self, lines: usize) -> Self {
+        if let Some(location) = self.location.as_mut() {
+            location.line += lines;
+        }
+        self
+    }
+
+    /// Re-anchors an error from a slice of a line onto the whole line, where the slice
+    /// started `columns` characters in.
+    pub fn within(mut self, source: &str, columns: usize) -> Self {
+        if let Some(location) = self.location.as_mut() {
+            location.column += columns;
+            location.source = source.to_string();
+        }
+        self
+    }
+}
+
+impl Display for AocError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let location = match &self.location {
+            Some(location) => location,
+            None => return write!(f, "error: {}", self.message),
+        };
+
+        let gutter = location.line.to_string();
+        let padding = " ".repeat(gutter.len());
+        let underline = "^".repeat(location.text.chars().count().max(1));
+
+        writeln!(
+            f,
+            "error: {} (line {}, column {})",
+            self.message, location.line, location.column
+        )?;
+        writeln!(f, "{} |", padding)?;
+        writeln!(f, "{} | {}", gutter, location.source)?;
+        write!(
+            f,
+            "{} | {}{}",
+            padding,
+            " ".repeat(location.column.saturating_sub(1)),
+            underline
+        )
+    }
+}
+
+impl std::error::Error for AocError {}
+
+impl From<io::Error> for AocError {
+    fn from(err: io::Error) -> Self {
+        AocError::new(err.to_string())
+    }
+}
+
+/// Environment variable pointing at a directory laid out as `{root}/{day}/{name}.txt`.
+pub const INPUT_ROOT_VAR: &str = "AOC_INPUT_ROOT";
+
+/// The name of the puzzle input every day directory holds.
+pub const DEFAULT_INPUT: &str = "input";
+
+/// Where a puzzle input is read from.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum InputSource {
+    /// An explicit file on disk.
+    Path(PathBuf),
+    /// `{root}/{day}/{name}.txt`, where `root` usually comes from `AOC_INPUT_ROOT`.
+    Root {
+        root: PathBuf,
+        day: String,
+        name: String,
+    },
+    /// `{CARGO_MANIFEST_DIR}/src/bin/{day}/{name}.txt`, independent of the working directory.
+    ManifestDir {
+        day: String,
+        name: String,
+    },
+    Stdin,
+}
+
+impl InputSource {
+    /// Resolves the default input for a day: `AOC_INPUT_ROOT` when it is set, the crate's
+    /// own `src/bin` directory otherwise.
+    pub fn for_day(day: &str) -> Self {
+        InputSource::for_input(day, DEFAULT_INPUT)
+    }
+
+    /// Like [`InputSource::for_day`], for another file kept next to the day's input.
+    pub fn for_input(day: &str, name: &str) -> Self {
+        match env::var_os(INPUT_ROOT_VAR) {
+            Some(root) if !root.is_empty() => InputSource::Root {
+                root: PathBuf::from(root),
+                day: day.to_string(),
+                name: name.to_string(),
+            },
+            _ => InputSource::ManifestDir {
+                day: day.to_string(),
+                name: name.to_string(),
+            },
+        }
+    }
+
+    /// The file this source reads from, or `None` for stdin.
+    pub fn path(&self) -> Option<PathBuf> {
+        match self {
+            InputSource::Path(path) => Some(path.clone()),
+            InputSource::Root { root, day, name } => {
+                Some(root.join(day).join(format!("{}.txt", name)))
+            }
+            InputSource::ManifestDir { day, name } => Some(
+                Path::new(env!("CARGO_MANIFEST_DIR"))
+                    .join("src/bin")
+                    .join(day)
+                    .join(format!("{}.txt", name)),
+            ),
+            InputSource::Stdin => None,
+        }
+    }
+
+    pub fn read_to_string(&self) -> io::Result<String> {
+        let mut buffer = String::new();
+        self.open()?.read_to_string(&mut buffer)?;
+
+        Ok(buffer)
+    }
+
+    pub fn open(&self) -> io::Result<Box<dyn BufRead>> {
+        match self.path() {
+            Some(path) => fs::File::open(&path)
+                .map(|file| Box::new(BufReader::new(file)) as Box<dyn BufRead>)
+                .map_err(|err| {
+                    io::Error::new(
+                        err.kind(),
+                        format!("Couldn't read input {}: {}", path.display(), err),
+                    )
+                }),
+            None => Ok(Box::new(io::stdin().lock())),
+        }
+    }
+
+    pub fn lines(&self) -> io::Result<LineStream<Box<dyn BufRead>>> {
+        Ok(LineStream::new(self.open()?))
+    }
+}
+
+/// Reads lines one at a time, without the line terminator, so an input never has to be
+/// held in memory as a whole.
+pub struct LineStream<R> {
+    reader: R,
+    buffer: String,
+}
+
+impl<R: BufRead> LineStream<R> {
+    pub fn new(reader: R) -> Self {
+        Self {
+            reader,
+            buffer: String::new(),
+        }
+    }
+
+    /// The next line, borrowed from a buffer that is reused by the following call.
+    pub fn next_line(&mut self) -> io::Result<Option<&str>> {
+        self.buffer.clear();
+        if self.reader.read_line(&mut self.buffer)? == 0 {
+            return Ok(None);
+        }
 
-    fs::read_to_string(path)
+        let line = self.buffer.strip_suffix('\n').unwrap_or(&self.buffer);
+        Ok(Some(line.strip_suffix('\r').unwrap_or(line)))
+    }
 }
 
-pub fn get_lines(day: &str) -> std::io::Result<Vec<String>> {
-    Ok(get_data(day)?.lines().map(String::from).collect())
+impl<R: BufRead> Iterator for LineStream<R> {
+    type Item = io::Result<String>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.next_line()
+            .map(|line| line.map(String::from))
+            .transpose()
+    }
+}
+
+/// Anything a solver can read line by line: lines already in memory or a [`LineStream`].
+pub trait Lines {
+    /// Calls `f` with each 1-based line number and line, stopping at the first error.
+    fn try_for_each_line<F>(self, f: F) -> Result<(), AocError>
+    where
+        F: FnMut(usize, &str) -> Result<(), AocError>;
+}
+
+impl Lines for &str {
+    fn try_for_each_line<F>(self, mut f: F) -> Result<(), AocError>
+    where
+        F: FnMut(usize, &str) -> Result<(), AocError>,
+    {
+        self.lines()
+            .enumerate()
+            .try_for_each(|(idx, line)| f(idx + 1, line))
+    }
+}
+
+impl<S: AsRef<str>> Lines for &[S] {
+    fn try_for_each_line<F>(self, mut f: F) -> Result<(), AocError>
+    where
+        F: FnMut(usize, &str) -> Result<(), AocError>,
+    {
+        self.iter()
+            .enumerate()
+            .try_for_each(|(idx, line)| f(idx + 1, line.as_ref()))
+    }
+}
+
+impl<S: AsRef<str>> Lines for &Vec<S> {
+    fn try_for_each_line<F>(self, f: F) -> Result<(), AocError>
+    where
+        F: FnMut(usize, &str) -> Result<(), AocError>,
+    {
+        self.as_slice().try_for_each_line(f)
+    }
+}
+
+impl<R: BufRead> Lines for LineStream<R> {
+    fn try_for_each_line<F>(mut self, mut f: F) -> Result<(), AocError>
+    where
+        F: FnMut(usize, &str) -> Result<(), AocError>,
+    {
+        let mut line_number = 0;
+        while let Some(line) = self.next_line()? {
+            line_number += 1;
+            f(line_number, line)?;
+        }
+
+        Ok(())
+    }
+}
+
+pub fn get_data(day: &str) -> io::Result<String> {
+    InputSource::for_day(day).read_to_string()
+}
+
+pub fn get_lines(day: &str) -> io::Result<LineStream<Box<dyn BufRead>>> {
+    InputSource::for_day(day).lines()
+}
+
+#[cfg(test)]
+mod core_tests {
+    use crate::core::*;
+
+    #[test]
+    fn error_renders_caret_under_column() {
+        let err = AocError::at("Invalid section id", "x", 1, "x")
+            .within("2-x,6-8", 2)
+            .on_line(12);
+
+        assert_eq!(
+            err.to_string(),
+            "error: Invalid section id (line 12, column 3)
+   |
+12 | 2-x,6-8
+   |   ^"
+        );
+    }
+
+    #[test]
+    fn error_without_location() {
+        assert_eq!(AocError::new("No answer").to_string(), "error: No answer");
+    }
+
+    #[test]
+    fn manifest_dir_ignores_working_directory() {
+        let source = InputSource::ManifestDir {
+            day: "day-4".into(),
+            name: DEFAULT_INPUT.into(),
+        };
+        let path = source.path().expect("Expected a path");
+
+        assert!(path.is_absolute());
+        assert!(path.ends_with("src/bin/day-4/input.txt"));
+        assert!(source.read_to_string().is_ok());
+    }
+
+    #[test]
+    fn root_layout() {
+        let source = InputSource::Root {
+            root: PathBuf::from("/inputs"),
+            day: "day-3".into(),
+            name: "large".into(),
+        };
+
+        assert_eq!(
+            source.path(),
+            Some(PathBuf::from("/inputs/day-3/large.txt"))
+        );
+    }
+
+    #[test]
+    fn line_stream_strips_terminators() {
+        let mut stream = LineStream::new("a\r\nbc\n\nd".as_bytes());
+
+        assert_eq!(stream.next_line().expect(""), Some("a"));
+        assert_eq!(stream.next_line().expect(""), Some("bc"));
+        assert_eq!(
+            stream.map(|line| line.expect("")).collect::<Vec<String>>(),
+            vec!["", "d"]
+        );
+    }
+
+    #[test]
+    fn lines_are_numbered_from_one() {
+        let mut seen = vec![];
+        LineStream::new("x\ny\n".as_bytes())
+            .try_for_each_line(|number, line| {
+                seen.push((number, line.to_string()));
+                Ok(())
+            })
+            .expect("Could not read lines");
+
+        assert_eq!(seen, vec![(1, "x".to_string()), (2, "y".to_string())]);
+    }
+
+    #[test]
+    fn missing_file_names_the_path() {
+        let err = InputSource::Path(PathBuf::from("/does/not/exist.txt"))
+            .read_to_string()
+            .expect_err("Expected a missing file");
+
+        assert_eq!(err.kind(), io::ErrorKind::NotFound);
+        assert!(err.to_string().contains("/does/not/exist.txt"));
+    }
 }
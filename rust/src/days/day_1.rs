@@ -0,0 +1,98 @@
+use crate::core::{AocError, Lines};
+use crate::solution::Solution;
+use std::collections::BinaryHeap;
+
+/// Each elf's snacks, in input order. Elves are separated by blank lines.
+pub fn parse_elves(input: impl Lines) -> Result<Vec<Vec<u32>>, AocError> {
+    let mut elves: Vec<Vec<u32>> = vec![vec![]];
+
+    input.try_for_each_line(|number, line| {
+        if line.trim().is_empty() {
+            elves.push(vec![]);
+            return Ok(());
+        }
+
+        let calories = line.trim().parse().map_err(|_| {
+            AocError::at(
+                format!("Expected calories, found {:?}", line),
+                line,
+                1,
+                line,
+            )
+            .on_line(number)
+        })?;
+        if let Some(elf) = elves.last_mut() {
+            elf.push(calories);
+        }
+        Ok(())
+    })?;
+
+    elves.retain(|elf| !elf.is_empty());
+    Ok(elves)
+}
+
+/// The total calories carried by the `count` elves carrying the most.
+pub fn find_elf_with_most_calories(elves: &[Vec<u32>], count: usize) -> Result<u32, AocError> {
+    let mut heap: BinaryHeap<u32> = elves.iter().map(|elf| elf.iter().sum()).collect();
+    let mut result = 0;
+
+    for _ in 0..count {
+        result += heap.pop().ok_or_else(|| {
+            AocError::new(format!(
+                "Asked for the top {} elves, but there are only {}",
+                count,
+                elves.len()
+            ))
+        })?;
+    }
+
+    Ok(result)
+}
+
+pub struct Day1;
+
+impl Solution for Day1 {
+    type Input = Vec<Vec<u32>>;
+    type Answer = u32;
+
+    const DAY: u8 = 1;
+
+    fn parse(&self, input: &str) -> Result<Self::Input, AocError> {
+        parse_elves(input)
+    }
+
+    fn part_one(&self, input: &Self::Input) -> Result<Self::Answer, AocError> {
+        find_elf_with_most_calories(input, 1)
+    }
+
+    fn part_two(&self, input: &Self::Input) -> Result<Self::Answer, AocError> {
+        find_elf_with_most_calories(input, 3)
+    }
+}
+
+#[cfg(test)]
+mod day_1_tests {
+    use crate::days::day_1::*;
+
+    #[test]
+    fn groups_by_blank_lines() {
+        let elves = parse_elves("1000\n2000\n\n4000\n\n\n5000").expect("Could not parse");
+
+        assert_eq!(elves, vec![vec![1000, 2000], vec![4000], vec![5000]]);
+    }
+
+    #[test]
+    fn too_few_elves() {
+        let elves = parse_elves("1000\n\n2000").expect("Could not parse");
+
+        assert_eq!(find_elf_with_most_calories(&elves, 2), Ok(3000));
+        assert!(find_elf_with_most_calories(&elves, 3).is_err());
+    }
+
+    #[test]
+    fn bad_calories_are_located() {
+        let err = parse_elves("1000\nlots").expect_err("Expected bad calories");
+
+        assert_eq!(err.location.map(|location| location.line), Some(2));
+    }
+}
@@ -0,0 +1,233 @@
+use crate::core::{AocError, Lines};
+use crate::solution::Solution;
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum GameMove {
+    Rock,
+    Paper,
+    Scissors,
+}
+
+/// The second column of the strategy guide; what it means differs between the two parts.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Code {
+    X,
+    Y,
+    Z,
+}
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct GameMatch {
+    opponent_move: GameMove,
+    my_move: GameMove,
+}
+
+impl GameMatch {
+    pub fn new(opponent_move: GameMove, my_move: GameMove) -> Self {
+        Self {
+            opponent_move,
+            my_move,
+        }
+    }
+
+    pub fn compute_score(&self) -> u32 {
+        GameMoveResult::score_from_move(self.my_move)
+            + GameMoveResult::score_from_moves(self.opponent_move, self.my_move)
+    }
+}
+
+pub struct GameMoveResult;
+
+impl GameMoveResult {
+    pub fn score_from_move(game_move: GameMove) -> u32 {
+        match game_move {
+            GameMove::Rock => 1,
+            GameMove::Paper => 2,
+            GameMove::Scissors => 3,
+        }
+    }
+
+    pub fn score_from_moves(opponent_move: GameMove, my_move: GameMove) -> u32 {
+        let win_score = 6;
+        let draw_score = 3;
+        let lose_score = 0;
+
+        if opponent_move == my_move {
+            return draw_score;
+        }
+
+        match (opponent_move, my_move) {
+            (GameMove::Rock, GameMove::Paper)
+            | (GameMove::Paper, GameMove::Scissors)
+            | (GameMove::Scissors, GameMove::Rock) => win_score,
+            _ => lose_score,
+        }
+    }
+
+    pub fn code_to_move(input: &str) -> Option<GameMove> {
+        match input {
+            "A" => Some(GameMove::Rock),
+            "B" => Some(GameMove::Paper),
+            "C" => Some(GameMove::Scissors),
+            _ => None,
+        }
+    }
+
+    pub fn get_lose(game_move: GameMove) -> GameMove {
+        match game_move {
+            GameMove::Rock => GameMove::Scissors,
+            GameMove::Paper => GameMove::Rock,
+            GameMove::Scissors => GameMove::Paper,
+        }
+    }
+
+    pub fn get_win(game_move: GameMove) -> GameMove {
+        match game_move {
+            GameMove::Rock => GameMove::Paper,
+            GameMove::Paper => GameMove::Scissors,
+            GameMove::Scissors => GameMove::Rock,
+        }
+    }
+
+    /// Part one reads the code as the move to play.
+    pub fn code_as_move(code: Code) -> GameMove {
+        match code {
+            Code::X => GameMove::Rock,
+            Code::Y => GameMove::Paper,
+            Code::Z => GameMove::Scissors,
+        }
+    }
+
+    /// Part two reads the code as the outcome to aim for.
+    pub fn derive_move(opponent: GameMove, code: Code) -> GameMove {
+        match code {
+            Code::X => GameMoveResult::get_lose(opponent),
+            Code::Y => opponent,
+            Code::Z => GameMoveResult::get_win(opponent),
+        }
+    }
+}
+
+fn parse_round(line: &str) -> Result<(GameMove, Code), AocError> {
+    let fields: Vec<&str> = line.split(' ').collect();
+    let opponent = fields.first().copied().unwrap_or_default();
+    let code = fields.get(1).copied().unwrap_or_default();
+
+    if fields.len() != 2 {
+        return Err(AocError::at("Expected a round like `A Y`", line, 1, line));
+    }
+
+    let opponent_move = GameMoveResult::code_to_move(opponent).ok_or_else(|| {
+        AocError::at(
+            format!("Expected A, B or C, found {:?}", opponent),
+            line,
+            1,
+            opponent,
+        )
+    })?;
+    let code = match code {
+        "X" => Code::X,
+        "Y" => Code::Y,
+        "Z" => Code::Z,
+        _ => {
+            return Err(AocError::at(
+                format!("Expected X, Y or Z, found {:?}", code),
+                line,
+                opponent.chars().count() + 2,
+                code,
+            ))
+        }
+    };
+
+    Ok((opponent_move, code))
+}
+
+pub fn parse_rounds(input: impl Lines) -> Result<Vec<(GameMove, Code)>, AocError> {
+    let mut rounds = vec![];
+    input.try_for_each_line(|number, line| {
+        if !line.trim().is_empty() {
+            rounds.push(parse_round(line).map_err(|err| err.on_line(number))?);
+        }
+        Ok(())
+    })?;
+
+    Ok(rounds)
+}
+
+pub struct Day2;
+
+impl Solution for Day2 {
+    type Input = Vec<(GameMove, Code)>;
+    type Answer = u32;
+
+    const DAY: u8 = 2;
+
+    fn parse(&self, input: &str) -> Result<Self::Input, AocError> {
+        parse_rounds(input)
+    }
+
+    fn part_one(&self, input: &Self::Input) -> Result<Self::Answer, AocError> {
+        Ok(input
+            .iter()
+            .map(|(opponent, code)| {
+                GameMatch::new(*opponent, GameMoveResult::code_as_move(*code)).compute_score()
+            })
+            .sum())
+    }
+
+    fn part_two(&self, input: &Self::Input) -> Result<Self::Answer, AocError> {
+        Ok(input
+            .iter()
+            .map(|(opponent, code)| {
+                GameMatch::new(*opponent, GameMoveResult::derive_move(*opponent, *code))
+                    .compute_score()
+            })
+            .sum())
+    }
+}
+
+#[cfg(test)]
+mod day_2_tests {
+    use crate::days::day_2::*;
+
+    #[test]
+    fn scores_match_the_pony_solution() {
+        assert_eq!(
+            GameMatch::new(GameMove::Rock, GameMove::Paper).compute_score(),
+            8
+        );
+        assert_eq!(
+            GameMatch::new(GameMove::Paper, GameMove::Rock).compute_score(),
+            1
+        );
+        assert_eq!(
+            GameMatch::new(GameMove::Scissors, GameMove::Scissors).compute_score(),
+            6
+        );
+    }
+
+    #[test]
+    fn derives_moves_from_outcomes() {
+        assert_eq!(
+            GameMoveResult::derive_move(GameMove::Rock, Code::X),
+            GameMove::Scissors
+        );
+        assert_eq!(
+            GameMoveResult::derive_move(GameMove::Paper, Code::Y),
+            GameMove::Paper
+        );
+        assert_eq!(
+            GameMoveResult::derive_move(GameMove::Scissors, Code::Z),
+            GameMove::Rock
+        );
+    }
+
+    #[test]
+    fn bad_codes_are_located() {
+        let err = Day2.parse("A Y\nB Q").expect_err("Expected a bad code");
+        let location = err.location.expect("Expected a location");
+
+        assert_eq!((location.line, location.column), (2, 3));
+        assert_eq!(location.text, "Q");
+    }
+}
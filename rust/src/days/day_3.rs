@@ -0,0 +1,1639 @@
+use crate::core::{AocError, LineStream, Lines};
+use crate::set_algebra::SetAlgebra;
+use crate::solution::{Part, Solution};
+use std::fmt::{Display, Formatter};
+use std::fs;
+use std::io::BufRead;
+use std::ops::{BitAnd, BitOr};
+use std::path::Path;
+
+/// A set of ASCII rucksack items backed by a bitmask, bit `n` holding the character with
+/// code `n`. What an item is worth is up to a [`PriorityScheme`].
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, PartialEq)]
+pub struct ItemSet(u128);
+
+impl ItemSet {
+    pub const EMPTY: ItemSet = ItemSet(0);
+
+    fn bit(item: char) -> Option<u32> {
+        item.is_ascii().then_some(item as u32)
+    }
+
+    fn with_bit(self, bit: u32) -> ItemSet {
+        ItemSet(self.0 | 1 << bit)
+    }
+
+    pub fn contains(&self, item: char) -> bool {
+        ItemSet::bit(item).is_some_and(|bit| self.0 & (1 << bit) != 0)
+    }
+
+    pub fn intersection(self, other: ItemSet) -> ItemSet {
+        ItemSet(self.0 & other.0)
+    }
+
+    pub fn union(self, other: ItemSet) -> ItemSet {
+        ItemSet(self.0 | other.0)
+    }
+
+    pub fn len(&self) -> usize {
+        self.0.count_ones() as usize
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0 == 0
+    }
+
+    /// The items in ASCII order.
+    pub fn iter(&self) -> ItemSetIter {
+        ItemSetIter(self.0)
+    }
+}
+
+/// Any printable ASCII item; use [`PriorityScheme::item_set`] to only accept the items a
+/// scheme knows about.
+impl TryFrom<&str> for ItemSet {
+    type Error = AocError;
+
+    fn try_from(value: &str) -> Result<Self, Self::Error> {
+        value.chars().enumerate().try_fold(
+            ItemSet::EMPTY,
+            |set, (column, item)| match ItemSet::bit(item).filter(|_| item.is_ascii_graphic()) {
+                Some(bit) => Ok(set.with_bit(bit)),
+                None => Err(AocError::at(
+                    format!("Unexpected item {:?}, items must be printable ASCII", item),
+                    value,
+                    column + 1,
+                    &item.to_string(),
+                )),
+            },
+        )
+    }
+}
+
+impl BitAnd for ItemSet {
+    type Output = ItemSet;
+
+    fn bitand(self, rhs: ItemSet) -> ItemSet {
+        self.intersection(rhs)
+    }
+}
+
+impl BitOr for ItemSet {
+    type Output = ItemSet;
+
+    fn bitor(self, rhs: ItemSet) -> ItemSet {
+        self.union(rhs)
+    }
+}
+
+pub struct ItemSetIter(u128);
+
+impl Iterator for ItemSetIter {
+    type Item = char;
+
+    fn next(&mut self) -> Option<char> {
+        if self.0 == 0 {
+            return None;
+        }
+
+        let bit = self.0.trailing_zeros();
+        self.0 &= self.0 - 1;
+        Some(char::from(bit as u8))
+    }
+}
+
+impl SetAlgebra for ItemSet {
+    fn empty() -> Self {
+        ItemSet::EMPTY
+    }
+
+    fn intersect_with(&mut self, other: &Self) {
+        self.0 &= other.0;
+    }
+
+    fn union_with(&mut self, other: &Self) {
+        self.0 |= other.0;
+    }
+
+    fn difference_with(&mut self, other: &Self) {
+        self.0 &= !other.0;
+    }
+
+    fn symmetric_difference_with(&mut self, other: &Self) {
+        self.0 ^= other.0;
+    }
+}
+
+impl IntoIterator for ItemSet {
+    type Item = char;
+    type IntoIter = ItemSetIter;
+
+    fn into_iter(self) -> ItemSetIter {
+        self.iter()
+    }
+}
+
+/// What to do with an item the scheme has no priority for.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub enum UnknownItems {
+    /// Reject the rucksack, pointing at the item.
+    #[default]
+    Error,
+    /// Leave the item out of the rucksack, as if it wasn't there.
+    Skip,
+    /// Keep the item, but it's worth nothing.
+    Zero,
+}
+
+/// Priorities for ASCII items, and a policy for the items without one. Schemes are limited
+/// to ASCII because an [`ItemSet`] keeps one bit per character code: alphabets and mappings
+/// with any other character are rejected, and input items outside ASCII can only be skipped.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct PriorityScheme {
+    priorities: [Option<u64>; 128],
+    unknown: UnknownItems,
+}
+
+/// The puzzle's scheme: `a` through `z` are worth 1 through 26, `A` through `Z` 27 through 52.
+impl Default for PriorityScheme {
+    fn default() -> Self {
+        PriorityScheme::from_fn(|item| match item {
+            'a'..='z' => Some(item as u64 - 'a' as u64 + 1),
+            'A'..='Z' => Some(item as u64 - 'A' as u64 + 27),
+            _ => None,
+        })
+    }
+}
+
+impl PriorityScheme {
+    pub const STANDARD_ALPHABET: &'static str =
+        "abcdefghijklmnopqrstuvwxyzABCDEFGHIJKLMNOPQRSTUVWXYZ";
+
+    /// Asks `priority` about every ASCII character up front.
+    pub fn from_fn(priority: impl Fn(char) -> Option<u64>) -> Self {
+        let mut priorities = [None; 128];
+        for (code, slot) in priorities.iter_mut().enumerate() {
+            *slot = priority(char::from(code as u8));
+        }
+
+        Self {
+            priorities,
+            unknown: UnknownItems::default(),
+        }
+    }
+
+    /// Each item is worth its position in `alphabet`, counting from 1.
+    pub fn from_alphabet(alphabet: &str) -> Result<Self, AocError> {
+        let mut scheme = PriorityScheme::from_fn(|_| None);
+        for (column, item) in alphabet.chars().enumerate() {
+            scheme.assign(item, column as u64 + 1).map_err(|message| {
+                AocError::at(message, alphabet, column + 1, &item.to_string())
+            })?;
+        }
+
+        Ok(scheme)
+    }
+
+    /// A mapping with one `item priority` pair per line. Blank lines and lines starting with
+    /// `#` are ignored.
+    pub fn parse(input: &str) -> Result<Self, AocError> {
+        let mut scheme = PriorityScheme::from_fn(|_| None);
+
+        for (idx, line) in input.lines().enumerate() {
+            if line.trim().is_empty() || line.trim_start().starts_with('#') {
+                continue;
+            }
+
+            let invalid = |message: String, column: usize, text: &str| {
+                AocError::at(message, line, column, text).on_line(idx + 1)
+            };
+            let mut fields = line.split_whitespace();
+            let (item, priority) = match (fields.next(), fields.next(), fields.next()) {
+                (Some(item), Some(priority), None) => (item, priority),
+                _ => return Err(invalid("Expected `item priority`".to_string(), 1, line)),
+            };
+            let item_start = line.len() - line.trim_start().len();
+            let priority_start = line[item_start + item.len()..]
+                .find(priority)
+                .map_or(0, |idx| idx + item_start + item.len());
+            let column = |start: usize| line[..start].chars().count() + 1;
+            let (item_column, priority_column) = (column(item_start), column(priority_start));
+
+            let mut chars = item.chars();
+            let item = match (chars.next(), chars.next()) {
+                (Some(item), None) => item,
+                _ => {
+                    return Err(invalid(
+                        format!("Expected a single item, found {:?}", item),
+                        item_column,
+                        item,
+                    ))
+                }
+            };
+            let priority = priority.parse().map_err(|_| {
+                invalid(
+                    format!("Expected a priority, found {:?}", priority),
+                    priority_column,
+                    priority,
+                )
+            })?;
+
+            scheme
+                .assign(item, priority)
+                .map_err(|message| invalid(message, item_column, &item.to_string()))?;
+        }
+
+        Ok(scheme)
+    }
+
+    pub fn load(path: &Path) -> Result<Self, AocError> {
+        PriorityScheme::parse(&fs::read_to_string(path).map_err(|err| {
+            AocError::new(format!(
+                "Couldn't read priorities {}: {}",
+                path.display(),
+                err
+            ))
+        })?)
+    }
+
+    pub fn with_unknown(mut self, unknown: UnknownItems) -> Self {
+        self.unknown = unknown;
+        self
+    }
+
+    fn assign(&mut self, item: char, priority: u64) -> Result<(), String> {
+        let slot = ItemSet::bit(item)
+            .and_then(|bit| self.priorities.get_mut(bit as usize))
+            .ok_or_else(|| format!("Item {:?} is not ASCII", item))?;
+        if slot.is_some() {
+            return Err(format!("Item {:?} already has a priority", item));
+        }
+
+        *slot = Some(priority);
+        Ok(())
+    }
+
+    pub fn priority(&self, item: char) -> Option<u64> {
+        ItemSet::bit(item).and_then(|bit| self.priorities[bit as usize])
+    }
+
+    /// The items in `value`, following the unknown item policy. Zero-valued unknown items
+    /// still have to be ASCII to fit in the set.
+    pub fn item_set(&self, value: &str) -> Result<ItemSet, AocError> {
+        let mut set = ItemSet::EMPTY;
+
+        for (column, item) in value.chars().enumerate() {
+            let bit = match (self.priority(item), self.unknown, ItemSet::bit(item)) {
+                (Some(_), _, Some(bit)) | (None, UnknownItems::Zero, Some(bit)) => Ok(bit),
+                (None, UnknownItems::Skip, _) => continue,
+                (None, UnknownItems::Zero, None) => Err("it isn't ASCII, so it can't be kept"),
+                _ => Err("it has no priority"),
+            };
+
+            set = set.with_bit(bit.map_err(|reason| {
+                AocError::at(
+                    format!("Unexpected item {:?}, {}", item, reason),
+                    value,
+                    column + 1,
+                    &item.to_string(),
+                )
+            })?);
+        }
+
+        Ok(set)
+    }
+
+    /// The total priority of `items`, following the unknown item policy.
+    pub fn score(&self, items: ItemSet) -> Result<u64, AocError> {
+        items
+            .iter()
+            .try_fold(0, |total, item| match (self.priority(item), self.unknown) {
+                (Some(priority), _) => Ok(total + priority),
+                (None, UnknownItems::Skip | UnknownItems::Zero) => Ok(total),
+                (None, UnknownItems::Error) => {
+                    Err(AocError::new(format!("Item {:?} has no priority", item)))
+                }
+            })
+    }
+}
+
+#[derive(Debug)]
+pub struct Rucksack {
+    compartments: Vec<String>,
+    sets: Vec<ItemSet>,
+    full_set: ItemSet,
+}
+
+/// Splits the line into two compartments, scored by the default scheme.
+impl TryFrom<&str> for Rucksack {
+    type Error = AocError;
+
+    fn try_from(value: &str) -> Result<Self, Self::Error> {
+        Rucksack::parse(value, 2, &PriorityScheme::default())
+    }
+}
+
+impl Rucksack {
+    pub fn new(compartments: Vec<String>, scheme: &PriorityScheme) -> Result<Self, AocError> {
+        let source = compartments.concat();
+        let mut columns = 0;
+        let mut sets = Vec::with_capacity(compartments.len());
+
+        for compartment in &compartments {
+            sets.push(
+                scheme
+                    .item_set(compartment)
+                    .map_err(|err| err.within(&source, columns))?,
+            );
+            columns += compartment.chars().count();
+        }
+        let full_set = ItemSet::union_all(&sets);
+
+        Ok(Self {
+            compartments,
+            sets,
+            full_set,
+        })
+    }
+
+    /// Splits `line` into `compartments` compartments of the same size.
+    pub fn parse(
+        line: &str,
+        compartments: usize,
+        scheme: &PriorityScheme,
+    ) -> Result<Self, AocError> {
+        let items = line.chars().count();
+        if compartments == 0 || !items.is_multiple_of(compartments) {
+            return Err(AocError::at(
+                format!(
+                    "{} items don't split into {} equal compartments",
+                    items, compartments
+                ),
+                line,
+                1,
+                line,
+            ));
+        }
+
+        let size = items / compartments;
+        let items: Vec<char> = line.chars().collect();
+        let parts = (0..compartments)
+            .map(|idx| items[idx * size..(idx + 1) * size].iter().collect())
+            .collect();
+
+        Rucksack::new(parts, scheme)
+    }
+
+    pub fn compartments(&self) -> &[String] {
+        &self.compartments
+    }
+
+    pub fn full_set(&self) -> ItemSet {
+        self.full_set
+    }
+
+    /// Items found in every compartment.
+    pub fn common_items(&self) -> ItemSet {
+        ItemSet::intersection_all(&self.sets).unwrap_or_default()
+    }
+
+    pub fn find_common(&self) -> Vec<char> {
+        self.common_items().iter().collect()
+    }
+
+    pub fn priority_of_common_items(&self, scheme: &PriorityScheme) -> Result<u64, AocError> {
+        scheme.score(self.common_items())
+    }
+}
+
+fn parse_rucksack(
+    number: usize,
+    line: &str,
+    compartments: usize,
+    scheme: &PriorityScheme,
+) -> Result<Rucksack, AocError> {
+    Rucksack::parse(line, compartments, scheme).map_err(|err| err.on_line(number))
+}
+
+/// A group shares its badge; an empty group has none.
+fn priority_of_group(group: &[ItemSet], scheme: &PriorityScheme) -> Result<u64, AocError> {
+    ItemSet::intersection_all(group).map_or(Ok(0), |badge| scheme.score(badge))
+}
+
+pub fn solve_one(input: impl Lines) -> Result<u64, AocError> {
+    Day3::default().sum_common_items(input)
+}
+
+pub fn solve_two(input: impl Lines) -> Result<u64, AocError> {
+    Day3::default().sum_badges(input)
+}
+
+/// How rucksacks are laid out and scored. The puzzle has two compartments per rucksack and
+/// groups of three elves; the runner can change both with `--opt compartments=<k>` and
+/// `--opt group-size=<n>`.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Day3 {
+    pub compartments: usize,
+    pub group_size: usize,
+    pub scheme: PriorityScheme,
+    /// Validate the whole input and refuse to answer if anything is wrong with it, rather
+    /// than stopping at the first unreadable line (`--opt strict=true`).
+    pub strict: bool,
+    /// The elves are shuffled, so part two has to find the groups rather than take
+    /// consecutive lines (`--opt unordered=true`).
+    pub unordered: bool,
+}
+
+/// Day 3 input as the runner keeps it: the item sets of each rucksack, rather than the text.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct Rucksacks {
+    /// All the items in each rucksack, in input order, so line `n` is at `n - 1`.
+    full_sets: Vec<ItemSet>,
+    /// The items every compartment of each rucksack shares, in the same order.
+    common_sets: Vec<ItemSet>,
+    /// The last line read, to point at when it leaves a group incomplete.
+    last: (usize, String),
+    /// Everything wrong with the input, when read in strict mode.
+    violations: Vec<Violation>,
+}
+
+impl Rucksacks {
+    pub fn len(&self) -> usize {
+        self.full_sets.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.full_sets.is_empty()
+    }
+}
+
+impl Default for Day3 {
+    fn default() -> Self {
+        Self {
+            compartments: 2,
+            group_size: 3,
+            scheme: PriorityScheme::default(),
+            strict: false,
+            unordered: false,
+        }
+    }
+}
+
+impl Day3 {
+    /// Part one: the items shared by every compartment of each rucksack.
+    pub fn sum_common_items(&self, input: impl Lines) -> Result<u64, AocError> {
+        let mut validator = self.strict.then(|| Validator::new(self, false));
+        let mut total = 0;
+
+        input.try_for_each_line(|number, line| {
+            if validator.as_mut().is_some_and(|v| !v.check(number, line)) {
+                return Ok(());
+            }
+
+            total += parse_rucksack(number, line, self.compartments, &self.scheme)?
+                .priority_of_common_items(&self.scheme)?;
+            Ok(())
+        })?;
+
+        if let Some(validator) = validator {
+            refuse(validator.finish())?;
+        }
+        Ok(total)
+    }
+
+    /// Part two: the badge shared by each group of `group_size` consecutive elves. Every elf
+    /// has to be in a group.
+    pub fn sum_badges(&self, input: impl Lines) -> Result<u64, AocError> {
+        let group_size = self.group_size()?;
+        let mut total = 0;
+        let mut group: Vec<ItemSet> = Vec::with_capacity(group_size);
+        let mut last = (0, String::new());
+        let mut validator = self.strict.then(|| Validator::new(self, true));
+
+        input.try_for_each_line(|number, line| {
+            if validator.as_mut().is_some_and(|v| !v.check(number, line)) {
+                return Ok(());
+            }
+
+            group.push(parse_rucksack(number, line, self.compartments, &self.scheme)?.full_set());
+            if group.len() == group_size {
+                total += priority_of_group(&group, &self.scheme)?;
+                group.clear();
+            }
+
+            last.0 = number;
+            last.1.clear();
+            last.1.push_str(line);
+            Ok(())
+        })?;
+
+        if let Some(validator) = validator {
+            refuse(validator.finish())?;
+        }
+        if !group.is_empty() {
+            return Err(self.incomplete_group(group.len(), last.0, &last.1));
+        }
+
+        Ok(total)
+    }
+
+    /// Checks every line against what the puzzle promises, reporting all the problems
+    /// rather than stopping at the first.
+    pub fn validate(&self, input: impl Lines) -> Result<Vec<Violation>, AocError> {
+        let mut validator = Validator::new(self, true);
+        input.try_for_each_line(|number, line| {
+            validator.check(number, line);
+            Ok(())
+        })?;
+
+        Ok(validator.finish())
+    }
+
+    /// Reads the input a line at a time, keeping only the item sets each part scores rather
+    /// than the text. In strict mode the lines are validated as they
+    /// go, and problems are kept for the parts to refuse.
+    pub fn read(&self, input: impl Lines) -> Result<Rucksacks, AocError> {
+        let mut rucksacks = Rucksacks::default();
+        let mut validator = self.strict.then(|| Validator::new(self, true));
+
+        input.try_for_each_line(|number, line| {
+            rucksacks.last.0 = number;
+            rucksacks.last.1.clear();
+            rucksacks.last.1.push_str(line);
+            if validator.as_mut().is_some_and(|v| !v.check(number, line)) {
+                return Ok(());
+            }
+
+            let rucksack = parse_rucksack(number, line, self.compartments, &self.scheme)?;
+            rucksacks.common_sets.push(rucksack.common_items());
+            rucksacks.full_sets.push(rucksack.full_set());
+            Ok(())
+        })?;
+
+        if let Some(validator) = validator {
+            rucksacks.violations = validator.finish();
+        }
+        Ok(rucksacks)
+    }
+
+    /// Part one over rucksacks already read. Problems with groups don't stop it.
+    fn total_common_items(&self, rucksacks: &Rucksacks) -> Result<u64, AocError> {
+        refuse(
+            rucksacks
+                .violations
+                .iter()
+                .filter(|violation| !violation.kind.is_about_groups())
+                .cloned()
+                .collect(),
+        )?;
+
+        rucksacks
+            .common_sets
+            .iter()
+            .try_fold(0, |total, common| Ok(total + self.scheme.score(*common)?))
+    }
+
+    /// Part two over rucksacks already read, grouping consecutive elves.
+    fn total_badges(&self, rucksacks: &Rucksacks) -> Result<u64, AocError> {
+        refuse(rucksacks.violations.clone())?;
+
+        let group_size = self.group_size()?;
+        let mut total = 0;
+        for group in rucksacks.full_sets.chunks(group_size) {
+            if group.len() < group_size {
+                let (number, line) = &rucksacks.last;
+                return Err(self.incomplete_group(group.len(), *number, line));
+            }
+            total += priority_of_group(group, &self.scheme)?;
+        }
+
+        Ok(total)
+    }
+
+    /// `configure` refuses empty groups, but the fields can still be set directly.
+    fn group_size(&self) -> Result<usize, AocError> {
+        match self.group_size {
+            0 => Err(AocError::new("Groups need at least one elf")),
+            size => Ok(size),
+        }
+    }
+
+    fn incomplete_group(&self, elves: usize, number: usize, line: &str) -> AocError {
+        AocError::at(
+            format!(
+                "The last group only has {} of {} elves",
+                elves, self.group_size
+            ),
+            line,
+            1,
+            line,
+        )
+        .on_line(number)
+    }
+
+    fn report(
+        &self,
+        part: Part,
+        lines: (usize, usize),
+        members: Vec<String>,
+        common: ItemSet,
+    ) -> Result<Report, AocError> {
+        Ok(Report {
+            part,
+            lines,
+            members,
+            common: common
+                .iter()
+                .map(|item| (item, self.scheme.priority(item)))
+                .collect(),
+            score: self.scheme.score(common)?,
+        })
+    }
+
+    /// How part one scored each rucksack.
+    pub fn explain_rucksacks(&self, input: impl Lines) -> Result<Vec<Report>, AocError> {
+        let mut reports = vec![];
+        input.try_for_each_line(|number, line| {
+            let rucksack = parse_rucksack(number, line, self.compartments, &self.scheme)?;
+            reports.push(self.report(
+                Part::One,
+                (number, number),
+                rucksack.compartments().to_vec(),
+                rucksack.common_items(),
+            )?);
+            Ok(())
+        })?;
+
+        Ok(reports)
+    }
+
+    /// How part two scored each group.
+    pub fn explain_groups(&self, input: impl Lines) -> Result<Vec<Report>, AocError> {
+        let group_size = self.group_size()?;
+        let mut rucksacks = vec![];
+        input.try_for_each_line(|number, line| {
+            let rucksack = parse_rucksack(number, line, self.compartments, &self.scheme)?;
+            rucksacks.push((number, line.to_string(), rucksack.full_set()));
+            Ok(())
+        })?;
+
+        rucksacks
+            .chunks(group_size)
+            .map(|group| {
+                let (first, last) = (&group[0], &group[group.len() - 1]);
+                if group.len() < group_size {
+                    return Err(self.incomplete_group(group.len(), last.0, &last.1));
+                }
+
+                self.report(
+                    Part::Two,
+                    (first.0, last.0),
+                    group.iter().map(|(_, line, _)| line.clone()).collect(),
+                    ItemSet::intersection_all(group.iter().map(|(_, _, set)| *set))
+                        .unwrap_or_default(),
+                )
+            })
+            .collect()
+    }
+}
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum ViolationKind {
+    /// The line doesn't split into equal compartments.
+    UnevenLength { items: usize, compartments: usize },
+    /// An item without a priority, when the scheme treats those as errors.
+    UnknownItem { column: usize, item: char },
+    /// An item without a priority that can't be kept at zero, since it isn't ASCII.
+    NonAsciiItem { column: usize, item: char },
+    /// A rucksack's compartments don't share exactly one item.
+    CommonItems { count: usize },
+    /// A group's rucksacks don't share exactly one badge.
+    Badges { count: usize },
+    /// The input ended part way through a group.
+    IncompleteGroup { elves: usize, group_size: usize },
+}
+
+impl ViolationKind {
+    /// Whether it only matters once rucksacks are grouped, as in part two.
+    fn is_about_groups(&self) -> bool {
+        matches!(
+            self,
+            ViolationKind::Badges { .. } | ViolationKind::IncompleteGroup { .. }
+        )
+    }
+}
+
+/// Something wrong with the input, and the lines where it is.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Violation {
+    pub lines: (usize, usize),
+    pub kind: ViolationKind,
+}
+
+impl Display for Violation {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self.lines {
+            (first, last) if first == last => write!(f, "line {}: ", first)?,
+            (first, last) => write!(f, "lines {}-{}: ", first, last)?,
+        }
+
+        match &self.kind {
+            ViolationKind::UnevenLength {
+                items,
+                compartments,
+            } => write!(
+                f,
+                "{} items don't split into {} equal compartments",
+                items, compartments
+            ),
+            ViolationKind::UnknownItem { column, item } => {
+                write!(f, "item {:?} in column {} has no priority", item, column)
+            }
+            ViolationKind::NonAsciiItem { column, item } => {
+                write!(f, "item {:?} in column {} isn't ASCII", item, column)
+            }
+            ViolationKind::CommonItems { count } => {
+                write!(f, "compartments share {} items, expected exactly 1", count)
+            }
+            ViolationKind::Badges { count } => {
+                write!(f, "group shares {} items, expected exactly 1", count)
+            }
+            ViolationKind::IncompleteGroup { elves, group_size } => write!(
+                f,
+                "the last group only has {} of {} elves",
+                elves, group_size
+            ),
+        }
+    }
+}
+
+/// Collects violations a line at a time, so it can run alongside a streaming solve. Part one
+/// doesn't care how rucksacks group, so it can leave the group checks out.
+struct Validator<'a> {
+    day: &'a Day3,
+    violations: Vec<Violation>,
+    groups: bool,
+    group: Vec<ItemSet>,
+    group_start: usize,
+    last_line: usize,
+}
+
+impl<'a> Validator<'a> {
+    fn new(day: &'a Day3, groups: bool) -> Self {
+        Self {
+            day,
+            violations: vec![],
+            groups,
+            group: Vec::with_capacity(day.group_size),
+            group_start: 1,
+            last_line: 0,
+        }
+    }
+
+    fn violation(&mut self, lines: (usize, usize), kind: ViolationKind) {
+        self.violations.push(Violation { lines, kind });
+    }
+
+    /// Checks one rucksack, returning whether it could be scored on its own. Group problems
+    /// only show up once the group is complete.
+    fn check(&mut self, number: usize, line: &str) -> bool {
+        let scheme = &self.day.scheme;
+        let before = self.violations.len();
+        let mut items = ItemSet::EMPTY;
+
+        for (idx, item) in line.chars().enumerate() {
+            match (scheme.priority(item), scheme.unknown, ItemSet::bit(item)) {
+                (Some(_), _, Some(bit)) | (None, UnknownItems::Zero, Some(bit)) => {
+                    items = items.with_bit(bit)
+                }
+                (None, UnknownItems::Skip, _) => {}
+                (None, UnknownItems::Zero, None) => self.violation(
+                    (number, number),
+                    ViolationKind::NonAsciiItem {
+                        column: idx + 1,
+                        item,
+                    },
+                ),
+                _ => self.violation(
+                    (number, number),
+                    ViolationKind::UnknownItem {
+                        column: idx + 1,
+                        item,
+                    },
+                ),
+            }
+        }
+
+        let length = line.chars().count();
+        if !length.is_multiple_of(self.day.compartments) {
+            self.violation(
+                (number, number),
+                ViolationKind::UnevenLength {
+                    items: length,
+                    compartments: self.day.compartments,
+                },
+            );
+        }
+
+        let valid = self.violations.len() == before;
+        if valid {
+            if let Ok(rucksack) = Rucksack::parse(line, self.day.compartments, scheme) {
+                let count = rucksack.common_items().len();
+                if count != 1 {
+                    self.violation((number, number), ViolationKind::CommonItems { count });
+                }
+            }
+        }
+
+        if !self.groups {
+            return valid;
+        }
+        if self.group.is_empty() {
+            self.group_start = number;
+        }
+        self.group.push(items);
+        self.last_line = number;
+        if self.group.len() == self.day.group_size {
+            let count = ItemSet::intersection_all(&self.group)
+                .unwrap_or_default()
+                .len();
+            if count != 1 {
+                self.violation((self.group_start, number), ViolationKind::Badges { count });
+            }
+            self.group.clear();
+        }
+
+        valid
+    }
+
+    fn finish(mut self) -> Vec<Violation> {
+        if !self.group.is_empty() {
+            self.violation(
+                (self.group_start, self.last_line),
+                ViolationKind::IncompleteGroup {
+                    elves: self.group.len(),
+                    group_size: self.day.group_size,
+                },
+            );
+        }
+
+        self.violations
+    }
+}
+
+/// Turns a strict run's violations into a single error listing all of them.
+fn refuse(violations: Vec<Violation>) -> Result<(), AocError> {
+    if violations.is_empty() {
+        return Ok(());
+    }
+
+    let lines: Vec<String> = violations.iter().map(ToString::to_string).collect();
+    Err(AocError::new(format!(
+        "Refusing to solve, the input has {} problem(s):\n{}",
+        violations.len(),
+        lines.join("\n")
+    )))
+}
+
+impl Day3 {
+    /// Groups shuffled rucksacks so that every group shares exactly one badge. The members
+    /// of each group are line numbers; `None` means no such grouping exists.
+    pub fn discover_groups(&self, input: impl Lines) -> Result<Option<Vec<BadgeGroup>>, AocError> {
+        self.find_groups(&self.read(input)?)
+    }
+
+    fn find_groups(&self, rucksacks: &Rucksacks) -> Result<Option<Vec<BadgeGroup>>, AocError> {
+        refuse(rucksacks.violations.clone())?;
+
+        Ok(
+            find_badge_groups(&rucksacks.full_sets, self.group_size()?).map(|groups| {
+                groups
+                    .into_iter()
+                    .map(|group| BadgeGroup {
+                        members: group.members.iter().map(|idx| idx + 1).collect(),
+                        ..group
+                    })
+                    .collect()
+            }),
+        )
+    }
+
+    /// Part two for shuffled input: the badges of whichever grouping is found first.
+    pub fn sum_discovered_badges(&self, input: impl Lines) -> Result<u64, AocError> {
+        self.total_discovered_badges(&self.read(input)?)
+    }
+
+    fn total_discovered_badges(&self, rucksacks: &Rucksacks) -> Result<u64, AocError> {
+        let groups = self.find_groups(rucksacks)?.ok_or_else(|| {
+            AocError::new(format!(
+                "The rucksacks can't be split into groups of {} sharing exactly one badge",
+                self.group_size
+            ))
+        })?;
+
+        groups.iter().try_fold(0, |total, group| {
+            let members = group
+                .members
+                .iter()
+                .map(|member| rucksacks.full_sets[member - 1]);
+            let badge = ItemSet::intersection_all(members).unwrap_or_default();
+            Ok(total + self.scheme.score(badge)?)
+        })
+    }
+}
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct BadgeGroup {
+    pub badge: char,
+    /// Where the members are in the caller's input, in ascending order.
+    pub members: Vec<usize>,
+}
+
+/// Exact cover of the sets by groups sharing exactly one item (Knuth's Algorithm X), with
+/// the groups built as the search goes. The unplaced set with the fewest groups left to join
+/// is always placed next, so dead ends show up early, and a group is abandoned as soon as its
+/// members have nothing in common.
+struct GroupSearch<'a> {
+    sets: &'a [ItemSet],
+    size: usize,
+    placed: Vec<bool>,
+    chosen: Vec<BadgeGroup>,
+}
+
+impl<'a> GroupSearch<'a> {
+    /// Past this many options a set is as good a choice as any, and counting them all could
+    /// take longer than the search.
+    const ENOUGH_OPTIONS: usize = 8;
+
+    fn new(sets: &'a [ItemSet], size: usize) -> Self {
+        Self {
+            sets,
+            size,
+            placed: vec![false; sets.len()],
+            chosen: vec![],
+        }
+    }
+
+    /// The unplaced sets, other than `set`, that share an item with it.
+    fn partners(&self, set: usize) -> impl Iterator<Item = usize> + '_ {
+        (0..self.sets.len()).filter(move |other| {
+            *other != set
+                && !self.placed[*other]
+                && !(self.sets[set] & self.sets[*other]).is_empty()
+        })
+    }
+
+    /// How many groups `set` could still join, counting no further than `cap`.
+    fn options(&self, set: usize, cap: usize) -> usize {
+        let partners: Vec<usize> = self.partners(set).collect();
+        let mut count = 0;
+        self.count_groups(&partners, 0, self.sets[set], 1, cap, &mut count);
+        count
+    }
+
+    fn count_groups(
+        &self,
+        partners: &[usize],
+        from: usize,
+        common: ItemSet,
+        members: usize,
+        cap: usize,
+        count: &mut usize,
+    ) {
+        if members == self.size {
+            *count += usize::from(common.len() == 1);
+            return;
+        }
+
+        for idx in from..partners.len() {
+            if *count >= cap || partners.len() - idx < self.size - members {
+                return;
+            }
+            let common = common & self.sets[partners[idx]];
+            if !common.is_empty() {
+                self.count_groups(partners, idx + 1, common, members + 1, cap, count);
+            }
+        }
+    }
+
+    fn search(&mut self) -> bool {
+        let mut unplaced = (0..self.sets.len()).filter(|set| !self.placed[*set]);
+        let mut next = match unplaced.next() {
+            Some(next) => next,
+            None => return true,
+        };
+        let mut fewest = self.options(next, Self::ENOUGH_OPTIONS);
+        for set in unplaced {
+            if fewest <= 1 {
+                break;
+            }
+            let options = self.options(set, fewest);
+            if options < fewest {
+                (next, fewest) = (set, options);
+            }
+        }
+        if fewest == 0 {
+            return false;
+        }
+
+        let partners: Vec<usize> = self.partners(next).collect();
+        self.placed[next] = true;
+        if self.extend(&partners, 0, self.sets[next], &mut vec![next]) {
+            return true;
+        }
+        self.placed[next] = false;
+
+        false
+    }
+
+    /// Grows `members` with sets from `partners[from..]`, searching on from each group that
+    /// ends up with exactly one item in common.
+    fn extend(
+        &mut self,
+        partners: &[usize],
+        from: usize,
+        common: ItemSet,
+        members: &mut Vec<usize>,
+    ) -> bool {
+        if members.len() == self.size {
+            let badge = match (common.len(), common.iter().next()) {
+                (1, Some(badge)) => badge,
+                _ => return false,
+            };
+            let mut group = members.clone();
+            group.sort_unstable();
+            self.chosen.push(BadgeGroup {
+                badge,
+                members: group,
+            });
+
+            if self.search() {
+                return true;
+            }
+            self.chosen.pop();
+            return false;
+        }
+
+        let missing = self.size - members.len();
+        for (idx, partner) in partners.iter().enumerate().skip(from) {
+            if partners.len() - idx < missing {
+                break;
+            }
+            let common = common & self.sets[*partner];
+            if common.is_empty() {
+                continue;
+            }
+
+            members.push(*partner);
+            self.placed[*partner] = true;
+            if self.extend(partners, idx + 1, common, members) {
+                return true;
+            }
+            self.placed[*partner] = false;
+            members.pop();
+        }
+
+        false
+    }
+}
+
+/// Partitions `sets` into groups of `size` that each share exactly one item, the members
+/// being indices into `sets` and the groups ordered by their first member. `None` means
+/// there is no such partition.
+pub fn find_badge_groups(sets: &[ItemSet], size: usize) -> Option<Vec<BadgeGroup>> {
+    if size == 0 || !sets.len().is_multiple_of(size) {
+        return None;
+    }
+
+    let mut search = GroupSearch::new(sets, size);
+    if !search.search() {
+        return None;
+    }
+
+    let mut groups = search.chosen;
+    groups.sort_by_key(|group| group.members[0]);
+    Some(groups)
+}
+
+/// How one rucksack or one group was scored.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Report {
+    pub part: Part,
+    /// The first and last input line covered; a rucksack covers a single line.
+    pub lines: (usize, usize),
+    /// A rucksack's compartments, or a group's rucksacks.
+    pub members: Vec<String>,
+    /// Every item the members have in common, with its priority when the scheme has one.
+    pub common: Vec<(char, Option<u64>)>,
+    pub score: u64,
+}
+
+impl Report {
+    /// The puzzle promises exactly one common item per rucksack and per group.
+    pub fn is_unexpected(&self) -> bool {
+        self.common.len() != 1
+    }
+
+    /// The report as a single line of JSON.
+    pub fn to_json(&self) -> String {
+        let members: Vec<String> = self
+            .members
+            .iter()
+            .map(|member| json_string(member))
+            .collect();
+        let common: Vec<String> = self
+            .common
+            .iter()
+            .map(|(item, priority)| {
+                format!(
+                    "{{\"item\":{},\"priority\":{}}}",
+                    json_string(&item.to_string()),
+                    priority.map_or("null".to_string(), |priority| priority.to_string())
+                )
+            })
+            .collect();
+
+        format!(
+            "{{\"part\":{},\"first_line\":{},\"last_line\":{},\"members\":[{}],\"common\":[{}],\"score\":{},\"unexpected\":{}}}",
+            self.part,
+            self.lines.0,
+            self.lines.1,
+            members.join(","),
+            common.join(","),
+            self.score,
+            self.is_unexpected()
+        )
+    }
+}
+
+fn json_string(value: &str) -> String {
+    let mut json = String::with_capacity(value.len() + 2);
+    json.push('"');
+    for c in value.chars() {
+        match c {
+            '"' => json.push_str("\\\""),
+            '\\' => json.push_str("\\\\"),
+            c if c.is_control() => json.push_str(&format!("\\u{:04x}", c as u32)),
+            c => json.push(c),
+        }
+    }
+    json.push('"');
+
+    json
+}
+
+impl Solution for Day3 {
+    type Input = Rucksacks;
+    type Answer = u64;
+
+    const DAY: u8 = 3;
+
+    fn parse(&self, input: &str) -> Result<Self::Input, AocError> {
+        self.read(input)
+    }
+
+    fn parse_reader(&self, reader: &mut dyn BufRead) -> Result<Self::Input, AocError> {
+        self.read(LineStream::new(reader))
+    }
+
+    fn part_one(&self, input: &Self::Input) -> Result<Self::Answer, AocError> {
+        self.total_common_items(input)
+    }
+
+    fn part_two(&self, input: &Self::Input) -> Result<Self::Answer, AocError> {
+        match self.unordered {
+            true => self.total_discovered_badges(input),
+            false => self.total_badges(input),
+        }
+    }
+
+    fn configure(&mut self, key: &str, value: &str) -> Result<(), AocError> {
+        let count = value
+            .parse()
+            .ok()
+            .filter(|count| *count > 0)
+            .ok_or_else(|| {
+                AocError::new(format!("Expected a positive {}, found {:?}", key, value))
+            });
+        let flag = value
+            .parse()
+            .map_err(|_| AocError::new(format!("Expected true or false, found {:?}", value)));
+
+        match key {
+            "compartments" => self.compartments = count?,
+            "group-size" => self.group_size = count?,
+            "strict" => self.strict = flag?,
+            "unordered" => self.unordered = flag?,
+            _ => {
+                return Err(AocError::new(format!(
+                "Day 3 has no option {:?}, expected compartments, group-size, strict or unordered",
+                key
+            )))
+            }
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod day_3_tests {
+    use crate::core::{get_lines, LineStream};
+    use crate::days::day_3::*;
+
+    const INPUT: &str = "vJrwpWtwJgWrhcsFMMfFFhFp
+jqHRNqRjqzjGDLGLrsFMfFZSrLrFZsSL
+PmmdzqPrVvPwwTWBwg
+wMqvLMZHhHMvwLHjbvcjnnSBnvTQFn
+ttgJtRGJQctTZtZT
+CrZsJsPPZsGzwwsLwLmpwMDw";
+
+    #[test]
+    fn test_problem_one() {
+        let lines: Vec<String> = INPUT.split('\n').map(String::from).collect();
+        let result = solve_one(&lines).expect("Could not solve");
+
+        assert_eq!(result, 157)
+    }
+
+    #[test]
+    fn test_problem_two() {
+        let lines: Vec<String> = INPUT.split('\n').map(String::from).collect();
+        let result = solve_two(&lines).expect("Could not solve");
+
+        assert_eq!(result, 70)
+    }
+
+    #[test]
+    fn solves_from_a_stream() {
+        let stream = LineStream::new(INPUT.as_bytes());
+
+        assert_eq!(solve_two(stream), Ok(70));
+    }
+
+    #[test]
+    fn parses_from_a_reader() {
+        let day = Day3::default();
+        let rucksacks = day
+            .parse_reader(&mut INPUT.as_bytes())
+            .expect("Could not parse");
+
+        assert_eq!(rucksacks.len(), 6);
+        assert_eq!(day.part_one(&rucksacks), Ok(157));
+        assert_eq!(day.part_two(&rucksacks), Ok(70));
+
+        let incomplete = day.parse(&INPUT[..INPUT.rfind('\n').unwrap_or_default()]);
+        let err = day
+            .part_two(&incomplete.expect("Could not parse"))
+            .expect_err("Expected an incomplete group");
+        assert_eq!(err.location.map(|location| location.line), Some(5));
+    }
+
+    #[test]
+    fn item_set_operations() {
+        let lhs = ItemSet::try_from("vJrwpWtwJgWr").expect("Could not build set");
+        let rhs = ItemSet::try_from("hcsFMMfFFhFp").expect("Could not build set");
+
+        assert_eq!((lhs & rhs).iter().collect::<String>(), "p");
+        assert_eq!((lhs | rhs).len(), 14);
+        assert!(lhs.contains('J') && !lhs.contains('F') && !lhs.contains('1'));
+        assert_eq!(
+            ItemSet::try_from("za1AZ").map(|set| set.iter().collect::<String>()),
+            Ok("1AZaz".to_string())
+        );
+        assert_eq!(
+            ItemSet::difference_all([lhs, rhs]).map(|set| set.iter().collect::<String>()),
+            Some("JWgrtvw".to_string())
+        );
+    }
+
+    #[test]
+    fn unknown_item_is_located() {
+        let lines: Vec<String> = vec!["vJrwpWtwJgWr".into(), "jqHRNq1jqzjG".into()];
+        let err = solve_one(&lines).expect_err("Expected an invalid item");
+        let location = err.location.expect("Expected a location");
+
+        assert_eq!((location.line, location.column), (2, 7));
+        assert_eq!(location.text, "1");
+    }
+
+    #[test]
+    fn priority_schemes() {
+        let standard = PriorityScheme::default();
+        let alphabet = PriorityScheme::from_alphabet(PriorityScheme::STANDARD_ALPHABET)
+            .expect("Could not build scheme");
+        let items = ItemSet::try_from("zaAZ").expect("Could not build set");
+
+        assert_eq!(standard, alphabet);
+        assert_eq!(standard.score(items), Ok(106));
+        assert_eq!(
+            PriorityScheme::from_fn(|item| item.is_ascii_uppercase().then_some(1)).score(items),
+            Err(AocError::new("Item 'a' has no priority"))
+        );
+
+        let mapping = PriorityScheme::parse("# item priority\n0 10\n1 20\n")
+            .expect("Could not parse mapping");
+        let day = Day3 {
+            scheme: mapping,
+            ..Day3::default()
+        };
+        assert_eq!(day.sum_common_items(&["0110", "1001"][..]), Ok(60));
+        assert!(PriorityScheme::from_alphabet("abca").is_err());
+    }
+
+    #[test]
+    fn unknown_item_policies() {
+        let digits = PriorityScheme::from_alphabet("123").expect("Could not build scheme");
+        let lines = vec!["1x2y1z".to_string()];
+
+        let day = |scheme: PriorityScheme| Day3 {
+            scheme,
+            ..Day3::default()
+        };
+        let err = day(digits.clone())
+            .sum_common_items(&lines)
+            .expect_err("Expected an unknown item");
+        assert_eq!(err.location.map(|location| location.column), Some(2));
+        assert_eq!(
+            day(digits.clone().with_unknown(UnknownItems::Skip)).sum_common_items(&lines),
+            Ok(1)
+        );
+
+        let zero = digits.with_unknown(UnknownItems::Zero);
+        let rucksack = Rucksack::parse("1xyx2z", 2, &zero).expect("Could not parse");
+        assert_eq!(rucksack.find_common(), vec!['x']);
+        assert_eq!(rucksack.priority_of_common_items(&zero), Ok(0));
+    }
+
+    #[test]
+    fn non_ascii_items_follow_the_policy() {
+        let day = |unknown: UnknownItems| Day3 {
+            scheme: PriorityScheme::default().with_unknown(unknown),
+            strict: true,
+            ..Day3::default()
+        };
+        let lines = vec!["aéaé"];
+
+        assert_eq!(day(UnknownItems::Skip).sum_common_items(&lines), Ok(1));
+        let err = day(UnknownItems::Zero)
+            .sum_common_items(&lines)
+            .expect_err("Expected a non-ASCII item");
+        assert_eq!(err.message, "Refusing to solve, the input has 2 problem(s):\nline 1: item 'é' in column 2 isn't ASCII\nline 1: item 'é' in column 4 isn't ASCII");
+
+        let err = PriorityScheme::default()
+            .with_unknown(UnknownItems::Zero)
+            .item_set("aé")
+            .expect_err("Expected a non-ASCII item");
+        assert_eq!(
+            err.message,
+            "Unexpected item 'é', it isn't ASCII, so it can't be kept"
+        );
+        assert_eq!(err.location.map(|location| location.column), Some(2));
+        assert!(PriorityScheme::from_alphabet("aé").is_err());
+    }
+
+    #[test]
+    fn malformed_mapping_is_located() {
+        let err = PriorityScheme::parse("a 1\nb two").expect_err("Expected a bad priority");
+        let location = err.location.expect("Expected a location");
+
+        assert_eq!((location.line, location.column), (2, 3));
+        assert_eq!(location.text, "two");
+    }
+
+    #[test]
+    fn compartments_and_group_sizes() {
+        let mut day = Day3::default();
+        day.configure("compartments", "3")
+            .expect("Could not configure");
+        day.configure("group-size", "2")
+            .expect("Could not configure");
+
+        assert_eq!(day.sum_common_items(&["abcAbcaBc"][..]), Ok(3));
+        assert_eq!(day.sum_badges(&["abc", "bcf", "cdx", "dcy"][..]), Ok(12));
+        assert!(day.configure("group-size", "0").is_err());
+        assert!(day.configure("groups", "2").is_err());
+
+        let err = day
+            .sum_common_items(&["abc", "abcd"][..])
+            .expect_err("Expected an uneven split");
+        assert_eq!(err.location.map(|location| location.line), Some(2));
+    }
+
+    #[test]
+    fn empty_groups_are_an_error() {
+        let day = Day3 {
+            group_size: 0,
+            ..Day3::default()
+        };
+        let expected = Err(AocError::new("Groups need at least one elf"));
+
+        assert_eq!(day.explain_groups(INPUT).map(|_| ()), expected);
+        assert_eq!(day.sum_badges(INPUT), expected.clone().map(|_| 0));
+        assert_eq!(
+            day.parse(INPUT)
+                .and_then(|rucksacks| day.part_two(&rucksacks)),
+            expected.map(|_| 0)
+        );
+        assert!(day.discover_groups(INPUT).is_err());
+    }
+
+    #[test]
+    fn incomplete_group_is_an_error() {
+        let lines: Vec<&str> = INPUT.lines().take(4).collect();
+        let err = solve_two(&lines).expect_err("Expected an incomplete group");
+
+        assert_eq!(err.message, "The last group only has 1 of 3 elves");
+        assert_eq!(err.location.map(|location| location.line), Some(4));
+    }
+
+    #[test]
+    fn explains_scores() {
+        let day = Day3::default();
+        let rucksacks = day.explain_rucksacks(INPUT).expect("Could not explain");
+        let groups = day.explain_groups(INPUT).expect("Could not explain");
+
+        assert_eq!(rucksacks.len(), 6);
+        assert_eq!(rucksacks[0].members, vec!["vJrwpWtwJgWr", "hcsFMMfFFhFp"]);
+        assert_eq!(rucksacks[0].common, vec![('p', Some(16))]);
+        assert_eq!(groups[1].lines, (4, 6));
+        assert_eq!(groups[1].common, vec![('Z', Some(52))]);
+        assert!(!rucksacks
+            .iter()
+            .chain(&groups)
+            .any(|report| report.is_unexpected()));
+        assert_eq!(
+            groups[0].to_json(),
+            "{\"part\":2,\"first_line\":1,\"last_line\":3,\"members\":[\"vJrwpWtwJgWrhcsFMMfFFhFp\",\"jqHRNqRjqzjGDLGLrsFMfFZSrLrFZsSL\",\"PmmdzqPrVvPwwTWBwg\"],\"common\":[{\"item\":\"r\",\"priority\":18}],\"score\":18,\"unexpected\":false}"
+        );
+    }
+
+    #[test]
+    fn flags_unexpected_rucksacks() {
+        let zero = PriorityScheme::default().with_unknown(UnknownItems::Zero);
+        let day = Day3 {
+            scheme: zero,
+            ..Day3::default()
+        };
+        let reports = day
+            .explain_rucksacks(&["abcabd", "a\"b\"c\""][..])
+            .expect("Could not explain");
+
+        assert_eq!(reports[0].common, vec![('a', Some(1)), ('b', Some(2))]);
+        assert!(reports[0].is_unexpected());
+        assert_eq!(reports[1].score, 0);
+        assert!(reports[1]
+            .to_json()
+            .contains("\"members\":[\"a\\\"b\",\"\\\"c\\\"\"]"));
+        assert!(reports[1]
+            .to_json()
+            .contains("{\"item\":\"\\\"\",\"priority\":null}"));
+    }
+
+    #[test]
+    fn validation_reports_every_violation() {
+        let day = Day3::default();
+        let lines = vec!["abcabd", "vJrwpWtwJgWrhcsFMMfFFhFp", "ab1", "aaba"];
+        let violations = day.validate(&lines).expect("Could not validate");
+        let kinds: Vec<((usize, usize), ViolationKind)> = violations
+            .into_iter()
+            .map(|violation| (violation.lines, violation.kind))
+            .collect();
+
+        assert_eq!(
+            kinds,
+            vec![
+                ((1, 1), ViolationKind::CommonItems { count: 2 }),
+                (
+                    (3, 3),
+                    ViolationKind::UnknownItem {
+                        column: 3,
+                        item: '1'
+                    }
+                ),
+                (
+                    (3, 3),
+                    ViolationKind::UnevenLength {
+                        items: 3,
+                        compartments: 2
+                    }
+                ),
+                ((1, 3), ViolationKind::Badges { count: 0 }),
+                (
+                    (4, 4),
+                    ViolationKind::IncompleteGroup {
+                        elves: 1,
+                        group_size: 3
+                    }
+                ),
+            ]
+        );
+        assert!(day.validate(INPUT).expect("Could not validate").is_empty());
+    }
+
+    #[test]
+    fn strict_mode_refuses_invalid_input() {
+        let mut day = Day3::default();
+        day.configure("strict", "true")
+            .expect("Could not configure");
+        let lines = vec!["abcabd", "ab1", "abcb"];
+
+        let err = day
+            .sum_common_items(&lines)
+            .expect_err("Expected a refusal");
+        assert_eq!(
+            err.message,
+            "Refusing to solve, the input has 3 problem(s):\nline 1: compartments share 2 items, expected exactly 1\nline 2: item '1' in column 3 has no priority\nline 2: 3 items don't split into 2 equal compartments"
+        );
+        assert!(day
+            .sum_badges(&lines)
+            .expect_err("Expected a refusal")
+            .message
+            .ends_with("lines 1-3: group shares 2 items, expected exactly 1"));
+        assert_eq!(day.sum_badges(LineStream::new(INPUT.as_bytes())), Ok(70));
+        assert_eq!(Day3::default().sum_common_items(&["abcabd"][..]), Ok(3));
+    }
+
+    #[test]
+    fn discovers_shuffled_groups() {
+        let mut day = Day3::default();
+        day.configure("unordered", "true")
+            .expect("Could not configure");
+        let lines: Vec<&str> = INPUT.lines().collect();
+        let shuffled = vec![lines[3], lines[0], lines[5], lines[2], lines[4], lines[1]];
+
+        let groups = day
+            .discover_groups(&shuffled)
+            .expect("Could not parse")
+            .expect("Expected a grouping");
+        assert_eq!(
+            groups,
+            vec![
+                BadgeGroup {
+                    badge: 'Z',
+                    members: vec![1, 3, 5]
+                },
+                BadgeGroup {
+                    badge: 'r',
+                    members: vec![2, 4, 6]
+                },
+            ]
+        );
+        let rucksacks = day.read(&shuffled).expect("Could not parse");
+        assert_eq!(day.part_two(&rucksacks), Ok(70));
+    }
+
+    #[test]
+    fn discovers_larger_groups_in_the_puzzle_input() {
+        let mut day = Day3::default();
+        day.configure("group-size", "4")
+            .expect("Could not configure");
+        let lines = get_lines("day-3").expect("Could not read input");
+        let rucksacks = day.read(lines).expect("Could not parse");
+
+        let groups = day
+            .find_groups(&rucksacks)
+            .expect("Could not search")
+            .expect("Expected a grouping");
+        let mut members: Vec<usize> = groups
+            .iter()
+            .flat_map(|group| group.members.clone())
+            .collect();
+        members.sort_unstable();
+        assert_eq!(members, (1..=rucksacks.len()).collect::<Vec<_>>());
+        assert!(groups.iter().all(|group| {
+            let sets = group
+                .members
+                .iter()
+                .map(|member| rucksacks.full_sets[member - 1]);
+            ItemSet::intersection_all(sets).map(|common| common.iter().collect::<Vec<_>>())
+                == Some(vec![group.badge])
+        }));
+    }
+
+    #[test]
+    fn proves_there_is_no_grouping() {
+        let sets = |items: &[&str]| -> Vec<ItemSet> {
+            items
+                .iter()
+                .map(|items| ItemSet::try_from(*items).expect("Could not build set"))
+                .collect()
+        };
+
+        assert_eq!(find_badge_groups(&sets(&["ab", "ab", "cd", "ce"]), 2), None);
+        assert_eq!(find_badge_groups(&sets(&["ab", "ab", "cd"]), 2), None);
+        assert_eq!(
+            find_badge_groups(&sets(&["ab", "cd", "bc", "da"]), 2),
+            Some(vec![
+                BadgeGroup {
+                    badge: 'b',
+                    members: vec![0, 2]
+                },
+                BadgeGroup {
+                    badge: 'd',
+                    members: vec![1, 3]
+                },
+            ])
+        );
+    }
+}
@@ -0,0 +1,658 @@
+use crate::core::{AocError, LineStream, Lines};
+use crate::interval::{
+    count_containing_pairs, count_overlapping_pairs, Interval, IntervalIndex, IntervalSet,
+};
+use crate::solution::Solution;
+use std::fmt::{Display, Formatter};
+use std::io::BufRead;
+
+/// The sections an elf is assigned, both ends included.
+pub type Segment = Interval<u32>;
+
+/// Splits `value` on `separator`, pairing each field with the column it starts at.
+fn fields(value: &str, separator: char) -> Vec<(usize, &str)> {
+    let mut column = 0;
+
+    value
+        .split(separator)
+        .map(|field| {
+            let start = column;
+            column += field.chars().count() + 1;
+            (start, field)
+        })
+        .collect()
+}
+
+fn parse_section(value: &str, column: usize, field: &str) -> Result<u32, AocError> {
+    let located = |message: String, offset: usize, text: &str| {
+        AocError::at(message, value, column + offset + 1, text)
+    };
+
+    if field.is_empty() {
+        return Err(located("Missing section id".to_string(), 0, field));
+    }
+    if let Some((offset, space)) = field.chars().enumerate().find(|(_, c)| c.is_whitespace()) {
+        return Err(located(
+            format!("Unexpected whitespace in section id {:?}", field),
+            offset,
+            &space.to_string(),
+        ));
+    }
+
+    // `u32::from_str` takes a leading `+`, which isn't a section id.
+    if let Some((offset, other)) = field.chars().enumerate().find(|(_, c)| !c.is_ascii_digit()) {
+        return Err(located(
+            format!("Expected a section id, found {:?}", field),
+            offset,
+            &other.to_string(),
+        ));
+    }
+
+    field.parse().map_err(|_| {
+        located(
+            format!("Section id {} is more than {}", field, u32::MAX),
+            0,
+            field,
+        )
+    })
+}
+
+/// What to do with a segment like `8-2` that ends before it starts.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub enum ReversedRanges {
+    /// Reject the segment, since it would hold no sections.
+    #[default]
+    Error,
+    /// Read it as if the bounds were the right way round.
+    Swap,
+}
+
+/// Parses `start-end`, pointing at whatever is wrong with it.
+pub fn parse_segment(value: &str, reversed: ReversedRanges) -> Result<Segment, AocError> {
+    let (start, end) = match fields(value, '-').as_slice() {
+        [(_, "")] => return Err(AocError::at("Missing segment", value, 1, value)),
+        [(start_column, start), (end_column, end)] => (
+            parse_section(value, *start_column, start)?,
+            parse_section(value, *end_column, end)?,
+        ),
+        _ => return Err(AocError::at("Expected a segment like 2-4", value, 1, value)),
+    };
+
+    match (end < start, reversed) {
+        (false, _) => Ok(Segment::new(start, end)),
+        (true, ReversedRanges::Swap) => Ok(Segment::new(end, start)),
+        (true, ReversedRanges::Error) => Err(AocError::at(
+            format!("Segment {} ends before it starts", value),
+            value,
+            1,
+            value,
+        )),
+    }
+}
+
+impl TryFrom<&str> for Segment {
+    type Error = AocError;
+
+    fn try_from(value: &str) -> Result<Self, Self::Error> {
+        parse_segment(value, ReversedRanges::Error)
+    }
+}
+
+/// The segments assigned to the elves on one line, any number of them.
+#[derive(Clone, Debug)]
+pub struct SegmentGroup {
+    members: Vec<Segment>,
+}
+
+impl TryFrom<&str> for SegmentGroup {
+    type Error = AocError;
+
+    fn try_from(value: &str) -> Result<Self, Self::Error> {
+        SegmentGroup::parse(value, ReversedRanges::Error)
+    }
+}
+
+impl SegmentGroup {
+    pub fn new(members: Vec<Segment>) -> Self {
+        Self { members }
+    }
+
+    pub fn parse(value: &str, reversed: ReversedRanges) -> Result<Self, AocError> {
+        let members = fields(value, ',')
+            .into_iter()
+            .map(|(column, field)| {
+                parse_segment(field, reversed).map_err(|err| err.within(value, column))
+            })
+            .collect::<Result<Vec<Segment>, AocError>>()?;
+
+        Ok(SegmentGroup { members })
+    }
+
+    pub fn segments(&self) -> &[Segment] {
+        &self.members
+    }
+
+    /// Whether one member contains every other, which for a pair is either containing the
+    /// other.
+    pub fn contains(&self) -> bool {
+        let hull = self
+            .members
+            .iter()
+            .fold(Segment::new(1, 0), |hull, member| hull.hull(member));
+
+        self.members.iter().any(|member| member.contains(&hull))
+    }
+
+    /// How many pairs of members overlap.
+    pub fn overlapping_pairs(&self) -> usize {
+        count_overlapping_pairs(&self.members)
+    }
+
+    /// Whether any two members overlap, stopping at the first pair that does.
+    pub fn overlaps(&self) -> bool {
+        self.members.iter().enumerate().any(|(idx, member)| {
+            self.members[idx + 1..]
+                .iter()
+                .any(|other| member.overlaps(other))
+        })
+    }
+
+    /// The sections every member cleans, empty when there are none or no members.
+    pub fn common(&self) -> Segment {
+        let mut members = self.members.iter();
+        let first = members.next().copied().unwrap_or(Segment::new(1, 0));
+
+        members.fold(first, |common, member| common.intersection(member))
+    }
+}
+
+/// Every section at least one elf cleans.
+pub fn cleaned_sections(input: &[SegmentGroup]) -> IntervalSet<u32> {
+    assignments(input).into_iter().collect()
+}
+
+/// The sections nobody cleans, between the lowest and highest section anyone does.
+pub fn uncleaned_sections(input: &[SegmentGroup]) -> Vec<Segment> {
+    let cleaned = cleaned_sections(input);
+
+    match (cleaned.intervals().first(), cleaned.intervals().last()) {
+        (Some(first), Some(last)) => cleaned.gaps(first.hull(last)),
+        _ => vec![],
+    }
+}
+
+/// Every elf's segment, in input order.
+pub fn assignments(input: &[SegmentGroup]) -> Vec<Segment> {
+    input
+        .iter()
+        .flat_map(|group| group.segments().iter().copied())
+        .collect()
+}
+
+/// How many pairs of elves anywhere in the input have one segment inside the other, and how
+/// many overlap, rather than only the elves sharing a line.
+pub fn count_all_pairs(input: &[SegmentGroup]) -> (usize, usize) {
+    let segments = assignments(input);
+
+    (
+        count_containing_pairs(&segments),
+        count_overlapping_pairs(&segments),
+    )
+}
+
+/// One elf, by the line of its group and its place in the group, both from 1.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct Elf {
+    pub line: usize,
+    pub member: usize,
+}
+
+impl Display for Elf {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.pad(&format!("line {} #{}", self.line, self.member))
+    }
+}
+
+/// Finds the elves cleaning a section, or any of a range of sections.
+pub fn section_index(input: &[SegmentGroup]) -> IntervalIndex<u32, Elf> {
+    IntervalIndex::new(input.iter().enumerate().flat_map(|(idx, group)| {
+        group
+            .segments()
+            .iter()
+            .enumerate()
+            .map(move |(member, segment)| {
+                let elf = Elf {
+                    line: idx + 1,
+                    member: member + 1,
+                };
+                (*segment, elf)
+            })
+    }))
+}
+
+/// Draws groups the way the puzzle does: a row of sections per elf, a blank line between
+/// groups, and sections shared within a group marked `#`.
+///
+/// When the span is wider than `max_width`, each column stands for several sections and
+/// shows `=` when the elf cleans all of them and `:` when it cleans some.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Diagram {
+    /// The sections to draw, or every section the groups cover when `None`.
+    pub span: Option<Segment>,
+    pub max_width: usize,
+}
+
+impl Default for Diagram {
+    fn default() -> Self {
+        Self {
+            span: None,
+            max_width: 80,
+        }
+    }
+}
+
+/// The sections at least two members of the group clean.
+fn shared_sections(group: &SegmentGroup) -> IntervalSet<u32> {
+    let members = group.segments();
+
+    members
+        .iter()
+        .enumerate()
+        .flat_map(|(idx, a)| members[idx + 1..].iter().map(move |b| a.intersection(b)))
+        .collect()
+}
+
+impl Diagram {
+    pub fn render(&self, groups: &[SegmentGroup]) -> String {
+        let span = self.span.unwrap_or_else(|| {
+            assignments(groups)
+                .iter()
+                .fold(Segment::new(1, 0), |hull, segment| hull.hull(segment))
+        });
+        if span.is_empty() {
+            return String::new();
+        }
+
+        let per_column = span.len().div_ceil(self.max_width.max(1) as u64);
+        let columns = span.len().div_ceil(per_column);
+        let mut diagram = String::new();
+        if per_column > 1 {
+            diagram.push_str(&format!(
+                "{} sections per column from {}\n",
+                per_column,
+                span.start()
+            ));
+        }
+
+        for (idx, group) in groups.iter().enumerate() {
+            if idx > 0 {
+                diagram.push('\n');
+            }
+
+            let shared = shared_sections(group);
+            for member in group.segments() {
+                let row: String = (0..columns)
+                    .map(|column| {
+                        let first = span.start() as u64 + column * per_column;
+                        let last = (first + per_column - 1).min(span.end() as u64);
+                        let cell = Segment::new(first as u32, last as u32);
+                        Self::cell(member, &cell, &shared, per_column)
+                    })
+                    .collect();
+                diagram.push_str(&format!("{}  {}\n", row, member));
+            }
+        }
+
+        diagram
+    }
+
+    fn cell(member: &Segment, cell: &Segment, shared: &IntervalSet<u32>, per_column: u64) -> char {
+        let cleaned = member.intersection(cell);
+        if cleaned.is_empty() {
+            return '.';
+        }
+        if shared
+            .intervals()
+            .iter()
+            .any(|sections| sections.overlaps(&cleaned))
+        {
+            return '#';
+        }
+
+        match (per_column, cleaned == *cell) {
+            (1, _) => char::from_digit(cell.start() % 10, 10).unwrap_or('?'),
+            (_, true) => '=',
+            (_, false) => ':',
+        }
+    }
+}
+
+pub fn part_one(input: &[SegmentGroup]) -> usize {
+    input.iter().filter(|group| group.contains()).count()
+}
+
+pub fn part_two(input: &[SegmentGroup]) -> usize {
+    input.iter().filter(|group| group.overlaps()).count()
+}
+
+pub fn parse_groups(input: impl Lines) -> Result<Vec<SegmentGroup>, AocError> {
+    Day4::default().parse_groups(input)
+}
+
+/// Both answers in a single pass, without keeping the groups around.
+pub fn count_groups(input: impl Lines) -> Result<(usize, usize), AocError> {
+    Day4::default().count_groups(input)
+}
+
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub struct Day4 {
+    pub reversed: ReversedRanges,
+}
+
+impl Day4 {
+    pub fn parse_groups(&self, input: impl Lines) -> Result<Vec<SegmentGroup>, AocError> {
+        let mut groups = vec![];
+        input.try_for_each_line(|number, line| {
+            groups
+                .push(SegmentGroup::parse(line, self.reversed).map_err(|err| err.on_line(number))?);
+            Ok(())
+        })?;
+
+        Ok(groups)
+    }
+
+    pub fn count_groups(&self, input: impl Lines) -> Result<(usize, usize), AocError> {
+        let (mut contained, mut overlapping) = (0, 0);
+        input.try_for_each_line(|number, line| {
+            let group =
+                SegmentGroup::parse(line, self.reversed).map_err(|err| err.on_line(number))?;
+            contained += group.contains() as usize;
+            overlapping += group.overlaps() as usize;
+            Ok(())
+        })?;
+
+        Ok((contained, overlapping))
+    }
+}
+
+impl Solution for Day4 {
+    type Input = Vec<SegmentGroup>;
+    type Answer = usize;
+
+    const DAY: u8 = 4;
+
+    fn parse(&self, input: &str) -> Result<Self::Input, AocError> {
+        self.parse_groups(input)
+    }
+
+    fn parse_reader(&self, reader: &mut dyn BufRead) -> Result<Self::Input, AocError> {
+        self.parse_groups(LineStream::new(reader))
+    }
+
+    fn part_one(&self, input: &Self::Input) -> Result<Self::Answer, AocError> {
+        Ok(part_one(input))
+    }
+
+    fn part_two(&self, input: &Self::Input) -> Result<Self::Answer, AocError> {
+        Ok(part_two(input))
+    }
+
+    fn configure(&mut self, key: &str, value: &str) -> Result<(), AocError> {
+        match key {
+            "normalize" => {
+                self.reversed = match value.parse() {
+                    Ok(true) => ReversedRanges::Swap,
+                    Ok(false) => ReversedRanges::Error,
+                    Err(_) => {
+                        return Err(AocError::new(format!(
+                            "Expected true or false, found {:?}",
+                            value
+                        )))
+                    }
+                }
+            }
+            _ => {
+                return Err(AocError::new(format!(
+                    "Day 4 has no option {:?}, expected normalize",
+                    key
+                )))
+            }
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod day_4_tests {
+    use crate::days::day_4::*;
+    use crate::interval::containing_pairs;
+
+    const TEST_INPUT: &str = "2-4,6-8
+2-3,4-5
+5-7,7-9
+2-8,3-7
+6-6,4-6
+2-6,4-8";
+
+    #[test]
+    fn example_one() {
+        let lines = TEST_INPUT
+            .lines()
+            .map(SegmentGroup::try_from)
+            .collect::<Result<Vec<SegmentGroup>, AocError>>()
+            .expect("");
+
+        assert_eq!(part_one(&lines), 2);
+    }
+
+    #[test]
+    fn example_two() {
+        let lines = TEST_INPUT
+            .lines()
+            .map(SegmentGroup::try_from)
+            .collect::<Result<Vec<SegmentGroup>, AocError>>()
+            .expect("");
+
+        assert_eq!(part_two(&lines), 4);
+    }
+
+    #[test]
+    fn counts_from_a_stream() {
+        let stream = LineStream::new(TEST_INPUT.as_bytes());
+
+        assert_eq!(count_groups(stream), Ok((2, 4)));
+    }
+
+    #[test]
+    fn malformed_section_is_located() {
+        let err = Day4::default()
+            .parse("2-4,6-8\n2-3,4-x5")
+            .expect_err("Expected a malformed section");
+        let location = err.location.expect("Expected a location");
+
+        assert_eq!((location.line, location.column), (2, 7));
+        assert_eq!(location.text, "x");
+        assert_eq!(location.source, "2-3,4-x5");
+    }
+
+    #[test]
+    fn sections_beyond_u16() {
+        let groups = parse_groups("70000-90000,80000-80001").expect("Could not parse");
+
+        assert_eq!(part_one(&groups), 1);
+        assert_eq!(groups[0].segments()[0].len(), 20_001);
+    }
+
+    #[test]
+    fn finds_uncleaned_sections() {
+        let groups = parse_groups("2-4,6-8\n7-9,12-12\n3-3,15-20").expect("Could not parse");
+        let cleaned = cleaned_sections(&groups);
+
+        assert_eq!(cleaned.coverage(), 14);
+        assert!(cleaned.covers(9) && !cleaned.covers(5));
+        assert_eq!(
+            uncleaned_sections(&groups),
+            vec![
+                Segment::new(5, 5),
+                Segment::new(10, 11),
+                Segment::new(13, 14)
+            ]
+        );
+        assert!(uncleaned_sections(&parse_groups(TEST_INPUT).expect("Could not parse")).is_empty());
+    }
+
+    #[test]
+    fn counts_pairs_across_lines() {
+        let groups = parse_groups(TEST_INPUT).expect("Could not parse");
+
+        assert_eq!(count_all_pairs(&groups), (29, 49));
+
+        // The two part one pairs are among them.
+        let containing = containing_pairs(&assignments(&groups));
+        assert!(containing.contains(&(6, 7)) && containing.contains(&(8, 9)));
+    }
+
+    #[test]
+    fn finds_elves_by_section() {
+        let index = section_index(&parse_groups(TEST_INPUT).expect("Could not parse"));
+        let elves = |found: Vec<(&Segment, &Elf)>| -> Vec<String> {
+            found.into_iter().map(|(_, elf)| elf.to_string()).collect()
+        };
+
+        assert_eq!(elves(index.stab(9)), vec!["line 3 #2"]);
+        assert_eq!(
+            elves(index.stab(2)),
+            vec!["line 1 #1", "line 2 #1", "line 4 #1", "line 6 #1"]
+        );
+        assert_eq!(elves(index.overlapping(&Segment::new(8, 20))).len(), 4);
+        assert!(index.stab(1).is_empty());
+    }
+
+    #[test]
+    fn groups_of_any_size() {
+        let groups =
+            parse_groups("2-8,3-4,5-7\n1-3\n2-4,3-6,4-9,4-5\n1-2,3-4").expect("Could not parse");
+
+        let contained: Vec<bool> = groups.iter().map(SegmentGroup::contains).collect();
+        assert_eq!(contained, vec![true, true, false, false]);
+
+        let overlapping: Vec<usize> = groups.iter().map(SegmentGroup::overlapping_pairs).collect();
+        assert_eq!(overlapping, vec![2, 0, 6, 0]);
+        assert!(groups
+            .iter()
+            .all(|group| group.overlaps() == (group.overlapping_pairs() > 0)));
+
+        assert_eq!(groups[2].common(), Segment::new(4, 4));
+        assert!(groups[0].common().is_empty());
+        assert_eq!(groups[1].common(), Segment::new(1, 3));
+        assert_eq!((part_one(&groups), part_two(&groups)), (2, 2));
+    }
+
+    #[test]
+    fn draws_like_the_puzzle() {
+        let groups = parse_groups(TEST_INPUT).expect("Could not parse");
+        let diagram = Diagram {
+            span: Some(Segment::new(1, 9)),
+            ..Diagram::default()
+        };
+
+        assert_eq!(
+            diagram.render(&groups[..3]),
+            ".234.....  2-4
+.....678.  6-8
+
+.23......  2-3
+...45....  4-5
+
+....56#..  5-7
+......#89  7-9
+"
+        );
+        assert_eq!(
+            Diagram::default().render(&groups[4..5]),
+            "..#  6-6\n45#  4-6\n"
+        );
+    }
+
+    #[test]
+    fn compresses_wide_spans() {
+        let groups = parse_groups("1-40,35-100\n1-45,60-100").expect("Could not parse");
+        let diagram = Diagram {
+            span: None,
+            max_width: 10,
+        };
+
+        assert_eq!(
+            diagram.render(&groups),
+            "10 sections per column from 1
+===#......  1-40
+...#======  35-100
+
+====:.....  1-45
+.....:====  60-100
+"
+        );
+    }
+
+    #[test]
+    fn invalid_segments_are_located() {
+        let located = |line: &str| {
+            let err = Day4::default()
+                .parse(line)
+                .expect_err("Expected an invalid segment");
+            let location = err.location.expect("Expected a location");
+            (err.message, location.column, location.text)
+        };
+
+        assert_eq!(
+            located("2-4,8-2"),
+            ("Segment 8-2 ends before it starts".into(), 5, "8-2".into())
+        );
+        assert_eq!(
+            located("2-4,6-4294967296"),
+            (
+                "Section id 4294967296 is more than 4294967295".into(),
+                7,
+                "4294967296".into()
+            )
+        );
+        assert_eq!(
+            located("2-,6-8"),
+            ("Missing section id".into(), 3, "".into())
+        );
+        assert_eq!(located("2-4,,6-8").0, "Missing segment");
+        assert_eq!(
+            located("2-4,+5-6"),
+            ("Expected a section id, found \"+5\"".into(), 5, "+".into())
+        );
+        assert_eq!(
+            located("2-4,6-8x"),
+            ("Expected a section id, found \"8x\"".into(), 8, "x".into())
+        );
+        assert_eq!(
+            located("2-4, 6-8"),
+            (
+                "Unexpected whitespace in section id \" 6\"".into(),
+                5,
+                " ".into()
+            )
+        );
+    }
+
+    #[test]
+    fn normalize_swaps_reversed_bounds() {
+        let mut day = Day4::default();
+        day.configure("normalize", "true")
+            .expect("Could not configure");
+        let groups = day.parse_groups("2-4,8-2").expect("Could not parse");
+
+        assert_eq!(
+            groups[0].segments(),
+            [Segment::new(2, 4), Segment::new(2, 8)]
+        );
+        assert_eq!(day.part_one(&groups), Ok(1));
+        assert!(day.configure("normalize", "maybe").is_err());
+        assert!(day.configure("reversed", "true").is_err());
+    }
+}
@@ -0,0 +1,600 @@
+use crate::core::AocError;
+use crate::solution::Solution;
+use std::fmt::{Debug, Display, Formatter};
+use std::rc::Rc;
+
+#[derive(Eq, PartialEq, Debug)]
+pub enum Value {
+    Int(usize),
+}
+#[derive(Eq, PartialEq, Debug)]
+pub struct MoveInstruction(Value, Value, Value);
+
+#[derive(Eq, PartialEq, Debug)]
+pub enum Instruction {
+    MoveInstruction(MoveInstruction),
+}
+
+#[derive(Eq, PartialEq, Debug)]
+pub enum ProgramNode {
+    Instruction(Instruction),
+}
+
+pub struct ProgramParser {
+    buffer: Vec<char>,
+    cursor: usize,
+}
+
+impl ProgramParser {
+    pub fn new(input: &str) -> Self {
+        Self {
+            buffer: input.chars().collect(),
+            cursor: 0,
+        }
+    }
+
+    fn current(&self) -> Option<char> {
+        if self.cursor == self.buffer.len() {
+            return None;
+        }
+
+        Some(self.buffer[self.cursor])
+    }
+
+    fn eat_next(&mut self) -> Option<char> {
+        match self.current() {
+            Some(val) => {
+                self.cursor += 1;
+                Some(val)
+            }
+            None => None,
+        }
+    }
+
+    fn eat(&mut self, symbol: char) -> Option<char> {
+        match self.current() {
+            Some(val) if val == symbol => {
+                self.cursor += 1;
+                Some(val)
+            }
+            _ => None,
+        }
+    }
+
+    /// Points at the token starting at `start`, reporting the line it sits on.
+    fn error_at(&self, start: usize, message: String) -> AocError {
+        let line_start = self.buffer[..start]
+            .iter()
+            .rposition(|c| *c == '\n')
+            .map_or(0, |idx| idx + 1);
+        let line_end = self.buffer[start..]
+            .iter()
+            .position(|c| *c == '\n')
+            .map_or(self.buffer.len(), |idx| start + idx);
+        let token_end = self.buffer[start..line_end]
+            .iter()
+            .position(|c| c.is_whitespace())
+            .map_or(line_end, |idx| start + idx);
+        let line = self.buffer[..start].iter().filter(|c| **c == '\n').count() + 1;
+
+        AocError::at(
+            message,
+            &self.buffer[line_start..line_end].iter().collect::<String>(),
+            start - line_start + 1,
+            &self.buffer[start..token_end].iter().collect::<String>(),
+        )
+        .on_line(line)
+    }
+
+    fn skip_whitespace(&mut self) {
+        while let Some(current) = self.current() {
+            if !current.is_whitespace() {
+                break;
+            }
+            self.cursor += 1;
+        }
+    }
+
+    fn parse_digit(&mut self) -> Result<usize, AocError> {
+        self.skip_whitespace();
+        let start = self.cursor;
+        let mut digit_buffer = String::from("");
+
+        while let Some(c) = self.current() {
+            if !c.is_ascii_digit() {
+                break;
+            }
+            self.eat_next();
+
+            digit_buffer.push(c)
+        }
+
+        digit_buffer
+            .parse()
+            .map_err(|_| self.error_at(start, "Expected a number".to_string()))
+    }
+
+    fn parse_int_value(&mut self, var_name: &str) -> Result<Value, AocError> {
+        self.skip_whitespace();
+        let start = self.cursor;
+
+        for c in var_name.chars() {
+            self.eat(c)
+                .ok_or_else(|| self.error_at(start, format!("Expected `{}`", var_name)))?;
+        }
+
+        Ok(Value::Int(self.parse_digit()?))
+    }
+
+    fn parse_instruction(&mut self) -> Result<ProgramNode, AocError> {
+        Ok(ProgramNode::Instruction(Instruction::MoveInstruction(
+            MoveInstruction(
+                self.parse_int_value("move")?,
+                self.parse_int_value("from")?,
+                self.parse_int_value("to")?,
+            ),
+        )))
+    }
+
+    pub fn parse_program(&mut self) -> Result<Vec<ProgramNode>, AocError> {
+        let mut program: Vec<ProgramNode> = vec![];
+        self.skip_whitespace();
+        while self.current().is_some() {
+            program.push(self.parse_instruction()?);
+            self.skip_whitespace();
+        }
+
+        Ok(program)
+    }
+}
+
+/// How a crane puts down the crates it lifts off a register.
+pub trait CraneModel: Debug {
+    /// Takes the crates lifted, bottom to top as they were stacked, and returns them in the
+    /// order they land on the other register, bottom first.
+    fn lift(&self, crates: Vec<char>) -> Vec<char>;
+}
+
+/// Moves one crate at a time, so the crates land in reverse.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct CrateMover9000;
+
+impl CraneModel for CrateMover9000 {
+    fn lift(&self, mut crates: Vec<char>) -> Vec<char> {
+        crates.reverse();
+        crates
+    }
+}
+
+/// Moves all the crates at once, keeping their order.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct CrateMover9001;
+
+impl CraneModel for CrateMover9001 {
+    fn lift(&self, crates: Vec<char>) -> Vec<char> {
+        crates
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct Vm {
+    registers: Vec<Vec<char>>,
+    crane: Rc<dyn CraneModel>,
+}
+
+impl Display for Vm {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let max_register_len = self
+            .registers
+            .iter()
+            .map(|x| x.len())
+            .max()
+            .unwrap_or_default();
+        let mut registers = String::new();
+        let register_list = (1..=self.registers.len())
+            .map(|x| format!(" {}  ", x))
+            .collect::<String>();
+        for i in 0..=max_register_len {
+            let i = max_register_len - i;
+            for register in self.registers.iter() {
+                match register.get(i) {
+                    Some(content) => registers.push_str(&format!("[{}] ", content)),
+                    _ => registers.push_str("    "),
+                }
+            }
+            registers.push('\n');
+        }
+
+        write!(
+            f,
+            "VM State -----\n{}{}\n------------",
+            registers, register_list
+        )
+    }
+}
+
+impl Vm {
+    /// A Vm over `registers`, moving crates with a CrateMover 9001.
+    pub fn new(registers: Vec<Vec<char>>) -> Self {
+        Self {
+            registers,
+            crane: Rc::new(CrateMover9001),
+        }
+    }
+
+    pub fn with_crane(mut self, crane: impl CraneModel + 'static) -> Self {
+        self.crane = Rc::new(crane);
+        self
+    }
+
+    fn exec_move(&mut self, m: usize, f: usize, t: usize) -> Result<(), AocError> {
+        if f >= self.registers.len() || t >= self.registers.len() {
+            return Err(AocError::new(format!(
+                "Can't move from {} to {}, there are {} registers",
+                f + 1,
+                t + 1,
+                self.registers.len()
+            )));
+        }
+
+        let from_length = self.registers[f].len();
+        if m > from_length {
+            return Err(AocError::new(format!(
+                "Can't move {} crates from register {}, it only holds {}",
+                m,
+                f + 1,
+                from_length
+            )));
+        }
+
+        let drain_range = from_length - m..from_length;
+        let lifted: Vec<char> = self.registers[f].drain(drain_range).collect();
+        let mut to_append = self.crane.lift(lifted);
+        self.registers[t].append(&mut to_append);
+
+        Ok(())
+    }
+
+    fn execute_instruction(&mut self, instruction: &Instruction) -> Result<(), AocError> {
+        match instruction {
+            Instruction::MoveInstruction(MoveInstruction(
+                Value::Int(m),
+                Value::Int(f),
+                Value::Int(t),
+            )) if *f > 0 && *t > 0 => self.exec_move(*m, f - 1, t - 1),
+            _ => Err(AocError::new(format!(
+                "Registers are numbered from 1, got {:?}",
+                instruction
+            ))),
+        }
+    }
+
+    pub fn run_program(&mut self, program: &[ProgramNode]) -> Result<(), AocError> {
+        for node in program {
+            match node {
+                ProgramNode::Instruction(instruction) => self.execute_instruction(instruction)?,
+            };
+        }
+
+        Ok(())
+    }
+
+    pub fn get_tops_of_stacks(&self) -> String {
+        self.registers
+            .iter()
+            .map(|reg| match reg.last() {
+                Some(c) => c.to_string(),
+                None => "".to_string(),
+            })
+            .collect()
+    }
+}
+
+pub struct StateParser {}
+
+impl StateParser {
+    /// The registers are labelled 1 to n along the bottom of the drawing.
+    fn count_registers(line: &str) -> Result<usize, AocError> {
+        let mut end = 0;
+        let mut count = 0;
+
+        for label in line.split_whitespace() {
+            let start = end + line[end..].find(label).unwrap_or(0);
+            end = start + label.len();
+            count += 1;
+
+            if label != count.to_string() {
+                return Err(AocError::at(
+                    format!("Expected register {}, found {:?}", count, label),
+                    line,
+                    line[..start].chars().count() + 1,
+                    label,
+                ));
+            }
+        }
+
+        match count {
+            0 => Err(AocError::at(
+                "Expected the register numbers below the drawing",
+                line,
+                1,
+                line,
+            )),
+            count => Ok(count),
+        }
+    }
+
+    fn parse_line(line: &str) -> Result<Vec<(usize, Option<char>)>, AocError> {
+        Ok(line
+            .chars()
+            .collect::<Vec<char>>()
+            .chunks(4)
+            .enumerate()
+            .map(|(idx, register)| match register.get(1) {
+                Some(value) if *value == ' ' => Ok(None),
+                Some(value) => Ok(Some(*value)),
+                None => Err(AocError::at(
+                    "Expected value in register, but got nothing",
+                    line,
+                    idx * 4 + 1,
+                    &register.iter().collect::<String>(),
+                )),
+            })
+            .collect::<Result<Vec<Option<char>>, AocError>>()?
+            .into_iter()
+            .enumerate()
+            .collect())
+    }
+
+    pub fn create_vm_registers(input: &[String]) -> Result<Vec<Vec<char>>, AocError> {
+        let (labels, drawing) = input
+            .split_last()
+            .ok_or_else(|| AocError::new("Expected a drawing of the registers"))?;
+        let n_registers =
+            StateParser::count_registers(labels).map_err(|err| err.on_line(input.len()))?;
+        let mut registers = vec![Vec::new(); n_registers];
+
+        for (idx, line) in drawing.iter().enumerate() {
+            let parsed = StateParser::parse_line(line).map_err(|err| err.on_line(idx + 1))?;
+            for (register, maybe_val) in parsed {
+                if let Some(c) = maybe_val {
+                    let stack = registers.get_mut(register).ok_or_else(|| {
+                        let text = c.to_string();
+                        AocError::at(
+                            format!("Crate {} is outside the {} registers", c, n_registers),
+                            &text,
+                            1,
+                            &text,
+                        )
+                        .within(line, register * 4 + 1)
+                        .on_line(idx + 1)
+                    })?;
+                    stack.insert(0, c)
+                }
+            }
+        }
+
+        Ok(registers)
+    }
+}
+
+pub fn parse_input(input: &str) -> Result<(Vm, Vec<ProgramNode>), AocError> {
+    let split = input
+        .split("\n\n")
+        .map(String::from)
+        .collect::<Vec<String>>();
+    let registers_string = split
+        .first()
+        .ok_or_else(|| AocError::new("Unexpected, couldn't find registers!"))?;
+    let mut program_parser = ProgramParser::new(split.get(1).ok_or_else(|| {
+        AocError::new("Unexpected, couldn't find program! It should follow a blank line")
+    })?);
+    let register_lines = registers_string
+        .lines()
+        .map(String::from)
+        .collect::<Vec<String>>();
+
+    Ok((
+        Vm::new(StateParser::create_vm_registers(&register_lines)?),
+        program_parser
+            .parse_program()
+            .map_err(|err| err.offset_lines(register_lines.len() + 1))?,
+    ))
+}
+
+pub struct Day5;
+
+impl Solution for Day5 {
+    type Input = (Vm, Vec<ProgramNode>);
+    type Answer = String;
+
+    const DAY: u8 = 5;
+
+    fn parse(&self, input: &str) -> Result<Self::Input, AocError> {
+        parse_input(input)
+    }
+
+    fn part_one(&self, (vm, program): &Self::Input) -> Result<Self::Answer, AocError> {
+        let mut vm = vm.clone().with_crane(CrateMover9000);
+        vm.run_program(program)?;
+
+        Ok(vm.get_tops_of_stacks())
+    }
+
+    fn part_two(&self, (vm, program): &Self::Input) -> Result<Self::Answer, AocError> {
+        let mut vm = vm.clone().with_crane(CrateMover9001);
+        vm.run_program(program)?;
+
+        Ok(vm.get_tops_of_stacks())
+    }
+}
+
+#[cfg(test)]
+mod day_5_tests {
+    use crate::days::day_5::*;
+
+    const INPUT: &str = "    [D]
+[N] [C]
+[Z] [M] [P]
+ 1   2   3
+
+move 1 from 2 to 1
+move 3 from 1 to 3
+move 2 from 2 to 1
+move 1 from 1 to 2";
+
+    #[test]
+    fn state_parser_smoke_test() {
+        let input = "[D]
+[N] [C]
+[Z] [M] [P]
+ 1   2   3 "
+            .lines()
+            .map(|x| x.to_string())
+            .collect::<Vec<String>>();
+
+        let registers = StateParser::create_vm_registers(&input).expect("Could not create VM");
+
+        assert_eq!(registers[0].len(), 3);
+        assert_eq!(registers[0][0], 'Z');
+        assert_eq!(registers.len(), 3);
+    }
+
+    #[test]
+    fn program_parser_smoke_test() {
+        let input = "move 1 from 2 to 1
+move 3 from 1 to 3
+move 2 from 2 to 1";
+
+        let mut parser = ProgramParser::new(input);
+        let program = parser.parse_program().expect("Could not parse program");
+
+        assert_eq!(
+            &program,
+            &[
+                ProgramNode::Instruction(Instruction::MoveInstruction(MoveInstruction(
+                    Value::Int(1),
+                    Value::Int(2),
+                    Value::Int(1),
+                ))),
+                ProgramNode::Instruction(Instruction::MoveInstruction(MoveInstruction(
+                    Value::Int(3),
+                    Value::Int(1),
+                    Value::Int(3),
+                ))),
+                ProgramNode::Instruction(Instruction::MoveInstruction(MoveInstruction(
+                    Value::Int(2),
+                    Value::Int(2),
+                    Value::Int(1),
+                ))),
+            ]
+        )
+    }
+
+    #[test]
+    fn vm_smoketest() {
+        let initial_state = "[D]
+[N] [C]
+[Z] [M] [P]
+ 1   2   3 "
+            .lines()
+            .map(|x| x.to_string())
+            .collect::<Vec<String>>();
+        let mut vm = Vm::new(
+            StateParser::create_vm_registers(&initial_state)
+                .expect("Could not create VM Registers"),
+        );
+
+        let instruction = Instruction::MoveInstruction(MoveInstruction(
+            Value::Int(1),
+            Value::Int(1),
+            Value::Int(2),
+        ));
+
+        vm.execute_instruction(&instruction)
+            .expect("Could not execute instruction");
+    }
+
+    #[test]
+    fn drawing_errors_are_located() {
+        let located = |drawing: &str| {
+            let input = format!("{}\n\nmove 1 from 1 to 1", drawing);
+            let err = parse_input(&input).expect_err("Expected a malformed drawing");
+            let location = err.location.expect("Expected a location");
+            (err.message, location.line, location.column, location.text)
+        };
+
+        assert_eq!(
+            located("[A]  x\n 1 "),
+            (
+                "Crate x is outside the 1 registers".into(),
+                1,
+                6,
+                "x".into()
+            )
+        );
+        assert_eq!(
+            located("[A] [B]\n 1   3 "),
+            ("Expected register 2, found \"3\"".into(), 2, 6, "3".into())
+        );
+        assert_eq!(
+            located("[A]\n   "),
+            (
+                "Expected the register numbers below the drawing".into(),
+                2,
+                1,
+                "   ".into()
+            )
+        );
+        assert_eq!(Vm::new(vec![]).get_tops_of_stacks(), "");
+        assert!(Vm::new(vec![]).to_string().starts_with("VM State"));
+    }
+
+    #[test]
+    fn program_errors_are_located() {
+        let input = INPUT.replace("move 2 from 2 to 1", "move 2 form 2 to 1");
+        let err = parse_input(&input).expect_err("Expected a malformed instruction");
+        let location = err.location.expect("Expected a location");
+
+        assert_eq!((location.line, location.column), (8, 8));
+        assert_eq!(location.text, "form");
+        assert_eq!(location.source, "move 2 form 2 to 1");
+    }
+
+    #[test]
+    fn part_one_example() {
+        let (vm, program) = parse_input(INPUT).expect("Could not create input");
+        let mut vm = vm.with_crane(CrateMover9000);
+        vm.run_program(&program).expect("Failed to run program");
+
+        assert_eq!(vm.get_tops_of_stacks(), "CMZ");
+    }
+
+    #[test]
+    fn part_two_example() {
+        let (mut vm, program) = parse_input(INPUT).expect("Could not create input");
+        vm.run_program(&program).expect("Failed to run program");
+
+        assert_eq!(vm.get_tops_of_stacks(), "MCD");
+    }
+
+    /// Puts the top crate down first, then the rest in the order they were stacked.
+    #[derive(Debug)]
+    struct TopFirst;
+
+    impl CraneModel for TopFirst {
+        fn lift(&self, mut crates: Vec<char>) -> Vec<char> {
+            crates.rotate_right(1);
+            crates
+        }
+    }
+
+    #[test]
+    fn user_defined_crane() {
+        let (vm, program) = parse_input(INPUT).expect("Could not create input");
+        let mut vm = vm.with_crane(TopFirst);
+        vm.run_program(&program).expect("Failed to run program");
+
+        assert_eq!(vm.get_tops_of_stacks(), "CMN");
+    }
+}
@@ -0,0 +1,77 @@
+use crate::core::AocError;
+use crate::solution::Solution;
+use std::collections::HashSet;
+
+pub fn get_start_of_packet_marker(
+    input: &str,
+    marker_len: usize,
+) -> Result<(usize, String), AocError> {
+    let mut set = HashSet::new();
+    let mut current_marker = String::default();
+
+    for (idx, c) in input.chars().enumerate() {
+        if set.len() == marker_len {
+            return Ok((idx, current_marker));
+        }
+
+        if set.contains(&c) {
+            let idx = current_marker
+                .find(c)
+                .ok_or_else(|| AocError::new(format!("Lost track of {:?} in the marker", c)))?;
+            let (removed, next) = current_marker.split_at(idx + c.len_utf8());
+
+            for to_remove in removed.chars() {
+                set.remove(&to_remove);
+            }
+
+            current_marker = next.into();
+        }
+
+        current_marker.push(c);
+        set.insert(c);
+    }
+
+    Err(AocError::new("No valid SOP marker"))
+}
+
+pub struct Day6;
+
+impl Solution for Day6 {
+    type Input = String;
+    type Answer = usize;
+
+    const DAY: u8 = 6;
+
+    fn parse(&self, input: &str) -> Result<Self::Input, AocError> {
+        Ok(input.trim().to_string())
+    }
+
+    fn part_one(&self, input: &Self::Input) -> Result<Self::Answer, AocError> {
+        Ok(get_start_of_packet_marker(input, 4)?.0)
+    }
+
+    fn part_two(&self, input: &Self::Input) -> Result<Self::Answer, AocError> {
+        Ok(get_start_of_packet_marker(input, 14)?.0)
+    }
+}
+
+#[cfg(test)]
+mod day_6_tests {
+    use crate::days::day_6::get_start_of_packet_marker;
+
+    #[test]
+    fn returns_marker_with_position() {
+        assert_eq!(
+            get_start_of_packet_marker("mjqjpqmgbljsphdztnvjfqwrcgsmlb", 4),
+            Ok((7, "jpqm".into()))
+        );
+        assert_eq!(
+            get_start_of_packet_marker("mjqjpqmgbljsphdztnvjfqwrcgsmlb", 14),
+            Ok((19, "qmgbljsphdztnv".into()))
+        );
+        assert_eq!(
+            get_start_of_packet_marker("ééaébcd", 4),
+            Ok((6, "aébc".into()))
+        );
+    }
+}
@@ -0,0 +1,21 @@
+use crate::solution::Registry;
+
+pub mod day_1;
+pub mod day_2;
+pub mod day_3;
+pub mod day_4;
+pub mod day_5;
+pub mod day_6;
+
+/// Every implemented day, keyed by day number.
+pub fn registry() -> Registry {
+    let mut registry = Registry::default();
+    registry.register(day_1::Day1);
+    registry.register(day_2::Day2);
+    registry.register(day_3::Day3::default());
+    registry.register(day_4::Day4::default());
+    registry.register(day_5::Day5);
+    registry.register(day_6::Day6);
+
+    registry
+}
@@ -0,0 +1,336 @@
+//! Puzzle examples kept as data in `src/bin/day-N/examples.txt`:
+//!
+//! ```text
+//! # Comments are allowed between examples.
+//! === first example
+//! --- input
+//! 2-4,6-8
+//! 2-3,4-5
+//! --- part 1
+//! 2
+//! --- part 2
+//! 4
+//! ```
+//!
+//! Every `===` line starts an example, followed by an `--- input` block and one
+//! `--- part N` block per expected answer. Blocks run until the next `---` or `===` line,
+//! with trailing blank lines dropped.
+
+use std::fmt::{Display, Formatter};
+use std::path::PathBuf;
+
+use crate::core::{AocError, InputSource};
+use crate::solution::{day_name, DynSolution, Part};
+
+pub const EXAMPLES_FILE: &str = "examples";
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Example {
+    pub name: String,
+    /// The line of the `===` header, for pointing back at the file.
+    pub line: usize,
+    pub input: String,
+    pub expected: Vec<(Part, String)>,
+}
+
+enum Block {
+    Input,
+    Part(Part),
+}
+
+#[derive(Default)]
+struct ExampleBuilder {
+    name: String,
+    line: usize,
+    input: Option<String>,
+    expected: Vec<(Part, String)>,
+}
+
+impl ExampleBuilder {
+    fn push(&mut self, block: &Block, body: &[&str]) {
+        let mut end = body.len();
+        while end > 0 && body[end - 1].trim().is_empty() {
+            end -= 1;
+        }
+        let text = body[..end].join("\n");
+
+        match block {
+            Block::Input => self.input = Some(text),
+            Block::Part(part) => self.expected.push((*part, text)),
+        }
+    }
+
+    fn build(self) -> Result<Example, AocError> {
+        let header = format!("=== {}", self.name);
+        let input = self.input.ok_or_else(|| {
+            AocError::at("Example has no `--- input` block", &header, 1, &header).on_line(self.line)
+        })?;
+
+        if self.expected.is_empty() {
+            return Err(
+                AocError::at("Example has no `--- part N` block", &header, 1, &header)
+                    .on_line(self.line),
+            );
+        }
+
+        Ok(Example {
+            name: self.name,
+            line: self.line,
+            input,
+            expected: self.expected,
+        })
+    }
+}
+
+pub fn parse_examples(text: &str) -> Result<Vec<Example>, AocError> {
+    let mut examples = vec![];
+    let mut current: Option<ExampleBuilder> = None;
+    let mut block: Option<(Block, Vec<&str>)> = None;
+
+    let flush = |current: &mut Option<ExampleBuilder>, block: &mut Option<(Block, Vec<&str>)>| {
+        if let (Some(example), Some((kind, body))) = (current.as_mut(), block.take()) {
+            example.push(&kind, &body);
+        }
+    };
+
+    for (idx, line) in text.lines().enumerate() {
+        let line_number = idx + 1;
+
+        if let Some(name) = line.strip_prefix("===") {
+            flush(&mut current, &mut block);
+            if let Some(example) = current.take() {
+                examples.push(example.build()?);
+            }
+            current = Some(ExampleBuilder {
+                name: name.trim().to_string(),
+                line: line_number,
+                ..ExampleBuilder::default()
+            });
+        } else if let Some(header) = line.strip_prefix("---") {
+            flush(&mut current, &mut block);
+            if current.is_none() {
+                return Err(
+                    AocError::at("Block outside of an example", line, 1, line).on_line(line_number)
+                );
+            }
+
+            let header = header.trim();
+            let kind = match header.strip_prefix("part") {
+                _ if header == "input" => Block::Input,
+                Some(part) => Block::Part(part.trim().parse().map_err(|err: String| {
+                    AocError::at(err, line, 1, line).on_line(line_number)
+                })?),
+                None => {
+                    return Err(
+                        AocError::at("Expected `--- input` or `--- part N`", line, 1, line)
+                            .on_line(line_number),
+                    )
+                }
+            };
+            block = Some((kind, vec![]));
+        } else if let Some((_, body)) = block.as_mut() {
+            body.push(line);
+        } else if !(line.trim().is_empty() || line.starts_with('#')) {
+            return Err(
+                AocError::at("Expected an `===` example header", line, 1, line)
+                    .on_line(line_number),
+            );
+        }
+    }
+
+    flush(&mut current, &mut block);
+    if let Some(example) = current.take() {
+        examples.push(example.build()?);
+    }
+
+    Ok(examples)
+}
+
+/// The checked-in examples file for a day. Unlike inputs, examples never come from
+/// `AOC_INPUT_ROOT`.
+pub fn examples_path(day: u8) -> PathBuf {
+    InputSource::ManifestDir {
+        day: day_name(day),
+        name: EXAMPLES_FILE.to_string(),
+    }
+    .path()
+    .unwrap_or_default()
+}
+
+#[derive(Clone, Debug)]
+pub struct ExampleOutcome {
+    pub day: u8,
+    pub name: String,
+    pub line: usize,
+    pub part: Part,
+    pub expected: String,
+    pub actual: Result<String, AocError>,
+}
+
+impl ExampleOutcome {
+    pub fn passed(&self) -> bool {
+        self.actual.as_ref() == Ok(&self.expected)
+    }
+}
+
+/// The failed case and a line diff of what was expected (`-`) against what was produced (`+`).
+impl Display for ExampleOutcome {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        writeln!(
+            f,
+            "day {} example {:?} (line {}) part {}:",
+            self.day, self.name, self.line, self.part
+        )?;
+
+        let actual = match &self.actual {
+            Ok(actual) => actual,
+            Err(err) => return write!(f, "{}", err),
+        };
+
+        let expected: Vec<&str> = self.expected.lines().collect();
+        let actual: Vec<&str> = actual.lines().collect();
+        for idx in 0..expected.len().max(actual.len()) {
+            match (expected.get(idx), actual.get(idx)) {
+                (Some(e), Some(a)) if e == a => writeln!(f, "  {}", e)?,
+                (e, a) => {
+                    if let Some(e) = e {
+                        writeln!(f, "- {}", e)?;
+                    }
+                    if let Some(a) = a {
+                        writeln!(f, "+ {}", a)?;
+                    }
+                }
+            }
+        }
+
+        Ok(())
+    }
+}
+
+pub fn run_examples(solution: &dyn DynSolution, examples: &[Example]) -> Vec<ExampleOutcome> {
+    examples
+        .iter()
+        .flat_map(|example| {
+            let parsed = solution.parse(&example.input);
+
+            example
+                .expected
+                .iter()
+                .map(move |(part, expected)| ExampleOutcome {
+                    day: solution.day(),
+                    name: example.name.clone(),
+                    line: example.line,
+                    part: *part,
+                    expected: expected.clone(),
+                    actual: parsed
+                        .as_ref()
+                        .map_err(Clone::clone)
+                        .and_then(|parsed| solution.run(parsed, *part)),
+                })
+                .collect::<Vec<ExampleOutcome>>()
+        })
+        .collect()
+}
+
+/// Loads and runs a day's examples file; a day without one has no examples.
+pub fn run_day_examples(solution: &dyn DynSolution) -> Result<Vec<ExampleOutcome>, AocError> {
+    let path = examples_path(solution.day());
+    if !path.exists() {
+        return Ok(vec![]);
+    }
+
+    let text = std::fs::read_to_string(&path)?;
+    let examples = parse_examples(&text).map_err(|err| AocError {
+        message: format!("{}: {}", path.display(), err.message),
+        ..err
+    })?;
+
+    Ok(run_examples(solution, &examples))
+}
+
+#[cfg(test)]
+mod examples_tests {
+    use crate::days::registry;
+    use crate::examples::*;
+
+    const EXAMPLES: &str = "# day 6
+=== first
+--- input
+mjqjpqmgbljsphdztnvjfqwrcgsmlb
+
+--- part 1
+7
+--- part 2
+19
+
+=== second
+--- input
+bvwbjplbgvbhsrlpgdmjqwftvncz
+--- part 2
+23
+";
+
+    #[test]
+    fn parse_format() {
+        let examples = parse_examples(EXAMPLES).expect("Could not parse examples");
+
+        assert_eq!(examples.len(), 2);
+        assert_eq!(examples[0].name, "first");
+        assert_eq!(examples[0].line, 2);
+        assert_eq!(examples[0].input, "mjqjpqmgbljsphdztnvjfqwrcgsmlb");
+        assert_eq!(
+            examples[0].expected,
+            vec![(Part::One, "7".to_string()), (Part::Two, "19".to_string())]
+        );
+        assert_eq!(examples[1].expected, vec![(Part::Two, "23".to_string())]);
+    }
+
+    #[test]
+    fn malformed_files_are_located() {
+        let err = parse_examples("=== broken\n--- input\nabc\n--- part 3\n1")
+            .expect_err("Expected an unknown part");
+        assert_eq!(err.location.map(|location| location.line), Some(4));
+
+        let err = parse_examples("=== no answers\n--- input\nabc\n")
+            .expect_err("Expected a missing part");
+        assert_eq!(err.location.map(|location| location.line), Some(1));
+    }
+
+    #[test]
+    fn failures_render_a_diff() {
+        let outcome = ExampleOutcome {
+            day: 5,
+            name: "stacks".into(),
+            line: 3,
+            part: Part::One,
+            expected: "CMZ".into(),
+            actual: Ok("MCD".into()),
+        };
+
+        assert!(!outcome.passed());
+        assert_eq!(
+            outcome.to_string(),
+            "day 5 example \"stacks\" (line 3) part 1:\n- CMZ\n+ MCD\n"
+        );
+    }
+
+    #[test]
+    fn all_examples_pass() {
+        let registry = registry();
+        let mut failures = vec![];
+
+        for solution in registry.iter() {
+            match run_day_examples(solution) {
+                Ok(outcomes) => failures.extend(
+                    outcomes
+                        .iter()
+                        .filter(|outcome| !outcome.passed())
+                        .map(ToString::to_string),
+                ),
+                Err(err) => failures.push(err.to_string()),
+            }
+        }
+
+        assert!(failures.is_empty(), "\n{}", failures.join("\n"));
+    }
+}
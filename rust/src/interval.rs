@@ -0,0 +1,935 @@
+//! Intervals over ordered values, either closed (`[start, end]`, the default) or half-open
+//! (`[start, end)`). An interval whose end comes before its start, or for half-open
+//! intervals at its start, is empty; all empty intervals are equal.
+//!
+//! Everything that needs to step between values, such as lengths or the difference of
+//! closed intervals, is only available for [`Discrete`] types.
+//!
+//! An [`IntervalSet`] merges any number of intervals into disjoint, sorted ones, and the
+//! pair functions find every overlapping or containing pair in a list of intervals in
+//! `O(n log n)`, plus the size of the answer when listing them. An [`IntervalIndex`] answers
+//! which intervals hold a point or meet a range in `O(log n)` plus the size of the answer.
+
+use std::collections::BTreeSet;
+use std::fmt::{Debug, Display, Formatter};
+use std::marker::PhantomData;
+
+/// Marker for which end points an interval includes.
+pub trait BoundKind: Copy + Debug + Default + Eq {
+    fn is_empty<T: Ord>(start: T, end: T) -> bool;
+}
+
+/// Both end points are included.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub struct Closed;
+
+/// The start is included, the end isn't.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub struct HalfOpen;
+
+impl BoundKind for Closed {
+    fn is_empty<T: Ord>(start: T, end: T) -> bool {
+        end < start
+    }
+}
+
+impl BoundKind for HalfOpen {
+    fn is_empty<T: Ord>(start: T, end: T) -> bool {
+        end <= start
+    }
+}
+
+/// Values with a next and previous value, like the integers.
+pub trait Discrete: Ord + Copy {
+    fn successor(self) -> Option<Self>;
+
+    fn predecessor(self) -> Option<Self>;
+
+    /// How many values there are from `start` up to, but not including, `end`.
+    fn steps(start: Self, end: Self) -> u64;
+}
+
+macro_rules! impl_discrete {
+    ($($t:ty),*) => {$(
+        impl Discrete for $t {
+            fn successor(self) -> Option<Self> {
+                self.checked_add(1)
+            }
+
+            fn predecessor(self) -> Option<Self> {
+                self.checked_sub(1)
+            }
+
+            fn steps(start: Self, end: Self) -> u64 {
+                match end > start {
+                    true => (end as i128 - start as i128) as u64,
+                    false => 0,
+                }
+            }
+        }
+    )*};
+}
+
+impl_discrete!(u8, u16, u32, u64, usize, i8, i16, i32, i64, isize);
+
+#[derive(Clone, Copy)]
+pub struct Interval<T, B = Closed> {
+    start: T,
+    end: T,
+    bounds: PhantomData<B>,
+}
+
+impl<T: Ord + Copy, B: BoundKind> Interval<T, B> {
+    pub fn new(start: T, end: T) -> Self {
+        Self {
+            start,
+            end,
+            bounds: PhantomData,
+        }
+    }
+
+    pub fn start(&self) -> T {
+        self.start
+    }
+
+    pub fn end(&self) -> T {
+        self.end
+    }
+
+    pub fn is_empty(&self) -> bool {
+        B::is_empty(self.start, self.end)
+    }
+
+    pub fn contains_point(&self, point: T) -> bool {
+        !self.is_empty() && self.start <= point && !B::is_empty(point, self.end)
+    }
+
+    /// Whether `other` lies entirely inside this interval. Every interval contains the
+    /// empty interval.
+    pub fn contains(&self, other: &Self) -> bool {
+        other.is_empty() || (self.start <= other.start && other.end <= self.end)
+    }
+
+    /// Whether the two share at least one value.
+    pub fn overlaps(&self, other: &Self) -> bool {
+        !self.intersection(other).is_empty()
+    }
+
+    pub fn intersection(&self, other: &Self) -> Self {
+        Interval::new(self.start.max(other.start), self.end.min(other.end))
+    }
+
+    /// The smallest interval covering both.
+    pub fn hull(&self, other: &Self) -> Self {
+        match (self.is_empty(), other.is_empty()) {
+            (true, _) => *other,
+            (_, true) => *self,
+            _ => Interval::new(self.start.min(other.start), self.end.max(other.end)),
+        }
+    }
+}
+
+impl<T: Discrete> Interval<T, Closed> {
+    /// How many values the interval holds, saturating at `u64::MAX`.
+    pub fn len(&self) -> u64 {
+        match self.is_empty() {
+            true => 0,
+            false => T::steps(self.start, self.end).saturating_add(1),
+        }
+    }
+
+    /// Whether nothing fits between the two, so they could be one interval.
+    fn touches(&self, other: &Self) -> bool {
+        self.overlaps(other)
+            || self.end.successor() == Some(other.start)
+            || other.end.successor() == Some(self.start)
+    }
+
+    /// Both intervals as one, when they overlap or are adjacent.
+    pub fn union(&self, other: &Self) -> Option<Self> {
+        match self.is_empty() || other.is_empty() || self.touches(other) {
+            true => Some(self.hull(other)),
+            false => None,
+        }
+    }
+
+    /// What's left of this interval once `other` is taken out: nothing, one piece, or the
+    /// pieces either side of `other`.
+    pub fn difference(&self, other: &Self) -> Vec<Self> {
+        if self.is_empty() {
+            return vec![];
+        }
+        if !self.overlaps(other) {
+            return vec![*self];
+        }
+
+        let before = other
+            .start
+            .predecessor()
+            .filter(|_| self.start < other.start)
+            .map(|end| Interval::new(self.start, end));
+        let after = other
+            .end
+            .successor()
+            .filter(|_| other.end < self.end)
+            .map(|start| Interval::new(start, self.end));
+
+        before.into_iter().chain(after).collect()
+    }
+}
+
+impl<T: Discrete> Interval<T, HalfOpen> {
+    pub fn len(&self) -> u64 {
+        T::steps(self.start, self.end)
+    }
+}
+
+impl<T: Ord + Copy> Interval<T, HalfOpen> {
+    /// Both intervals as one, when they overlap or one ends where the other starts.
+    pub fn union(&self, other: &Self) -> Option<Self> {
+        let touches = self.end == other.start || other.end == self.start;
+
+        match self.is_empty() || other.is_empty() || touches || self.overlaps(other) {
+            true => Some(self.hull(other)),
+            false => None,
+        }
+    }
+
+    pub fn difference(&self, other: &Self) -> Vec<Self> {
+        if self.is_empty() {
+            return vec![];
+        }
+        if !self.overlaps(other) {
+            return vec![*self];
+        }
+
+        [
+            Interval::new(self.start, other.start),
+            Interval::new(other.end, self.end),
+        ]
+        .into_iter()
+        .filter(|piece| !piece.is_empty())
+        .collect()
+    }
+}
+
+impl<T: Ord + Copy, B: BoundKind> PartialEq for Interval<T, B> {
+    fn eq(&self, other: &Self) -> bool {
+        match (self.is_empty(), other.is_empty()) {
+            (true, true) => true,
+            (false, false) => self.start == other.start && self.end == other.end,
+            _ => false,
+        }
+    }
+}
+
+impl<T: Ord + Copy, B: BoundKind> Eq for Interval<T, B> {}
+
+impl<T: Debug, B: BoundKind> Debug for Interval<T, B> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Interval")
+            .field("start", &self.start)
+            .field("end", &self.end)
+            .field("bounds", &B::default())
+            .finish()
+    }
+}
+
+/// `start-end`, the way the puzzle writes section ranges.
+impl<T: Display> Display for Interval<T, Closed> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.pad(&format!("{}-{}", self.start, self.end))
+    }
+}
+
+/// `[start, end)`, so it can't be mistaken for a closed interval.
+impl<T: Display> Display for Interval<T, HalfOpen> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.pad(&format!("[{}, {})", self.start, self.end))
+    }
+}
+
+/// What an [`IntervalSet`] needs from its intervals beyond ordering, which depends on how
+/// they're bounded.
+pub trait Span: Sized {
+    fn union(&self, other: &Self) -> Option<Self>;
+
+    fn difference(&self, other: &Self) -> Vec<Self>;
+
+    fn len(&self) -> u64;
+
+    fn is_empty(&self) -> bool;
+}
+
+impl<T: Discrete> Span for Interval<T, Closed> {
+    fn union(&self, other: &Self) -> Option<Self> {
+        Interval::<T, Closed>::union(self, other)
+    }
+
+    fn difference(&self, other: &Self) -> Vec<Self> {
+        Interval::<T, Closed>::difference(self, other)
+    }
+
+    fn len(&self) -> u64 {
+        Interval::<T, Closed>::len(self)
+    }
+
+    fn is_empty(&self) -> bool {
+        Interval::<T, Closed>::is_empty(self)
+    }
+}
+
+impl<T: Discrete> Span for Interval<T, HalfOpen> {
+    fn union(&self, other: &Self) -> Option<Self> {
+        Interval::<T, HalfOpen>::union(self, other)
+    }
+
+    fn difference(&self, other: &Self) -> Vec<Self> {
+        Interval::<T, HalfOpen>::difference(self, other)
+    }
+
+    fn len(&self) -> u64 {
+        Interval::<T, HalfOpen>::len(self)
+    }
+
+    fn is_empty(&self) -> bool {
+        Interval::<T, HalfOpen>::is_empty(self)
+    }
+}
+
+/// Intervals merged into disjoint, non-adjacent ones, sorted by start.
+#[derive(Clone)]
+pub struct IntervalSet<T, B = Closed> {
+    intervals: Vec<Interval<T, B>>,
+}
+
+impl<T: Ord + Copy, B: BoundKind> PartialEq for IntervalSet<T, B> {
+    fn eq(&self, other: &Self) -> bool {
+        self.intervals == other.intervals
+    }
+}
+
+impl<T: Ord + Copy, B: BoundKind> Eq for IntervalSet<T, B> {}
+
+impl<T: Debug, B: BoundKind> Debug for IntervalSet<T, B> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_set().entries(&self.intervals).finish()
+    }
+}
+
+impl<T, B> Default for IntervalSet<T, B> {
+    fn default() -> Self {
+        Self { intervals: vec![] }
+    }
+}
+
+impl<T, B> IntervalSet<T, B>
+where
+    T: Ord + Copy,
+    B: BoundKind,
+    Interval<T, B>: Span,
+{
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds an interval, merging it with any it overlaps or touches.
+    pub fn insert(&mut self, interval: Interval<T, B>) {
+        if interval.is_empty() {
+            return;
+        }
+
+        let mut merged = interval;
+        let mut intervals = Vec::with_capacity(self.intervals.len() + 1);
+        let mut placed = false;
+
+        for existing in self.intervals.drain(..) {
+            match merged.union(&existing) {
+                Some(union) => merged = union,
+                None if existing.start() < merged.start() => intervals.push(existing),
+                None => {
+                    if !placed {
+                        intervals.push(merged);
+                        placed = true;
+                    }
+                    intervals.push(existing);
+                }
+            }
+        }
+        if !placed {
+            intervals.push(merged);
+        }
+
+        self.intervals = intervals;
+    }
+
+    pub fn intervals(&self) -> &[Interval<T, B>] {
+        &self.intervals
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.intervals.is_empty()
+    }
+
+    /// How many values are covered.
+    pub fn coverage(&self) -> u64 {
+        self.intervals.iter().map(Span::len).sum()
+    }
+
+    pub fn covers(&self, point: T) -> bool {
+        let idx = self
+            .intervals
+            .partition_point(|interval| interval.start() <= point);
+
+        idx > 0 && self.intervals[idx - 1].contains_point(point)
+    }
+
+    /// The parts of `within` that nothing covers, in order.
+    pub fn gaps(&self, within: Interval<T, B>) -> Vec<Interval<T, B>> {
+        let mut gaps = vec![];
+        let mut remaining = match within.is_empty() {
+            true => return gaps,
+            false => within,
+        };
+
+        for interval in &self.intervals {
+            if !remaining.overlaps(interval) {
+                match interval.start() < remaining.start() {
+                    true => continue,
+                    false => break,
+                }
+            }
+
+            let mut after = None;
+            for piece in remaining.difference(interval) {
+                match piece.start() < interval.start() {
+                    true => gaps.push(piece),
+                    false => after = Some(piece),
+                }
+            }
+            match after {
+                Some(piece) => remaining = piece,
+                None => return gaps,
+            }
+        }
+
+        gaps.push(remaining);
+        gaps
+    }
+}
+
+/// Sorts and merges in one sweep, rather than inserting one at a time.
+impl<T, B> FromIterator<Interval<T, B>> for IntervalSet<T, B>
+where
+    T: Ord + Copy,
+    B: BoundKind,
+    Interval<T, B>: Span,
+{
+    fn from_iter<I: IntoIterator<Item = Interval<T, B>>>(iter: I) -> Self {
+        let mut sorted: Vec<Interval<T, B>> = iter
+            .into_iter()
+            .filter(|interval| !interval.is_empty())
+            .collect();
+        sorted.sort_by_key(|interval| interval.start());
+
+        let mut intervals: Vec<Interval<T, B>> = Vec::with_capacity(sorted.len());
+        for interval in sorted {
+            match intervals.last().and_then(|last| last.union(&interval)) {
+                Some(union) => *intervals.last_mut().expect("union needs a last interval") = union,
+                None => intervals.push(interval),
+            }
+        }
+
+        Self { intervals }
+    }
+}
+
+/// Two intervals by their index in a list, lower index first.
+pub type IndexPair = (usize, usize);
+
+/// Counts inserted values by rank, for the pairs that need "how many so far are at least x".
+struct Fenwick(Vec<usize>);
+
+impl Fenwick {
+    fn new(size: usize) -> Self {
+        Self(vec![0; size + 1])
+    }
+
+    fn add(&mut self, rank: usize) {
+        let mut idx = rank + 1;
+        while idx < self.0.len() {
+            self.0[idx] += 1;
+            idx += idx & idx.wrapping_neg();
+        }
+    }
+
+    /// How many inserted values rank below `rank`.
+    fn count_below(&self, rank: usize) -> usize {
+        let (mut idx, mut count) = (rank, 0);
+        while idx > 0 {
+            count += self.0[idx];
+            idx -= idx & idx.wrapping_neg();
+        }
+        count
+    }
+}
+
+/// Indices of the non-empty intervals, by start and then longest first, so anything that
+/// contains an interval comes before it.
+fn sweep_order<T: Ord + Copy, B: BoundKind>(intervals: &[Interval<T, B>]) -> Vec<usize> {
+    let mut order: Vec<usize> = (0..intervals.len())
+        .filter(|&idx| !intervals[idx].is_empty())
+        .collect();
+    order.sort_by(|&a, &b| {
+        let (a, b) = (&intervals[a], &intervals[b]);
+        a.start.cmp(&b.start).then(b.end.cmp(&a.end))
+    });
+    order
+}
+
+/// How many pairs of intervals share at least one value. Empty intervals overlap nothing.
+pub fn count_overlapping_pairs<T: Ord + Copy, B: BoundKind>(intervals: &[Interval<T, B>]) -> usize {
+    let order = sweep_order(intervals);
+    let mut ends: Vec<T> = order.iter().map(|&idx| intervals[idx].end).collect();
+    ends.sort();
+
+    // A pair is disjoint exactly when the one starting later starts after the other ends.
+    let disjoint: usize = order
+        .iter()
+        .map(|&idx| ends.partition_point(|&end| B::is_empty(intervals[idx].start, end)))
+        .sum();
+
+    order.len() * order.len().saturating_sub(1) / 2 - disjoint
+}
+
+/// How many pairs have one interval inside the other. Equal intervals count once, and empty
+/// intervals are left out.
+pub fn count_containing_pairs<T: Ord + Copy, B: BoundKind>(intervals: &[Interval<T, B>]) -> usize {
+    let order = sweep_order(intervals);
+    let mut ends: Vec<T> = order.iter().map(|&idx| intervals[idx].end).collect();
+    ends.sort();
+    ends.dedup();
+
+    let mut seen = Fenwick::new(ends.len());
+    let mut pairs = 0;
+    for (count, &idx) in order.iter().enumerate() {
+        let rank = ends.partition_point(|&end| end < intervals[idx].end);
+        // Everything before started no later, so it contains this one if it ends no earlier.
+        pairs += count - seen.count_below(rank);
+        seen.add(rank);
+    }
+
+    pairs
+}
+
+/// Every overlapping pair and every containing pair, as `(lower index, higher index)` and
+/// sorted, in one sweep over the intervals still open.
+fn sweep_pairs<T: Ord + Copy, B: BoundKind>(
+    intervals: &[Interval<T, B>],
+) -> (Vec<IndexPair>, Vec<IndexPair>) {
+    let (mut overlapping, mut containing) = (vec![], vec![]);
+    let mut open: BTreeSet<(T, usize)> = BTreeSet::new();
+
+    for idx in sweep_order(intervals) {
+        let interval = &intervals[idx];
+        while let Some(&(end, _)) = open.first() {
+            match B::is_empty(interval.start, end) {
+                true => open.pop_first(),
+                false => break,
+            };
+        }
+
+        let pair = |&(_, other): &(T, usize)| (other.min(idx), other.max(idx));
+        overlapping.extend(open.iter().map(pair));
+        containing.extend(open.range((interval.end, 0)..).map(pair));
+        open.insert((interval.end, idx));
+    }
+
+    overlapping.sort();
+    containing.sort();
+    (overlapping, containing)
+}
+
+/// Every pair of intervals sharing a value, as sorted `(lower index, higher index)`.
+pub fn overlapping_pairs<T: Ord + Copy, B: BoundKind>(
+    intervals: &[Interval<T, B>],
+) -> Vec<IndexPair> {
+    sweep_pairs(intervals).0
+}
+
+/// Every pair where one interval lies inside the other, as sorted `(lower index, higher
+/// index)`.
+pub fn containing_pairs<T: Ord + Copy, B: BoundKind>(
+    intervals: &[Interval<T, B>],
+) -> Vec<IndexPair> {
+    sweep_pairs(intervals).1
+}
+
+/// A static index over intervals that each carry a value.
+///
+/// The entries are sorted by start and read as an implicit balanced tree, the middle entry of
+/// every range being the root of that range, with the furthest end in each subtree alongside.
+/// A query skips any subtree that ends before it and everything right of an entry that starts
+/// after it.
+#[derive(Clone)]
+pub struct IntervalIndex<T, V, B = Closed> {
+    entries: Vec<(Interval<T, B>, V)>,
+    max_ends: Vec<T>,
+}
+
+impl<T: Ord + Copy, V, B: BoundKind> IntervalIndex<T, V, B> {
+    /// Indexes the non-empty intervals; empty ones could never match a query.
+    pub fn new(entries: impl IntoIterator<Item = (Interval<T, B>, V)>) -> Self {
+        let mut entries: Vec<(Interval<T, B>, V)> = entries
+            .into_iter()
+            .filter(|(interval, _)| !interval.is_empty())
+            .collect();
+        entries.sort_by_key(|(interval, _)| interval.start);
+
+        let mut max_ends: Vec<T> = entries.iter().map(|(interval, _)| interval.end).collect();
+        Self::fill_max_ends(&mut max_ends, 0, entries.len());
+
+        Self { entries, max_ends }
+    }
+
+    fn fill_max_ends(max_ends: &mut [T], lo: usize, hi: usize) -> Option<T> {
+        if lo >= hi {
+            return None;
+        }
+
+        let mid = lo + (hi - lo) / 2;
+        let left = Self::fill_max_ends(max_ends, lo, mid);
+        let right = Self::fill_max_ends(max_ends, mid + 1, hi);
+        let max = [left, right]
+            .into_iter()
+            .flatten()
+            .fold(max_ends[mid], T::max);
+        max_ends[mid] = max;
+
+        Some(max)
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Walks the implicit tree, `ends_before` and `starts_after` saying when a subtree or
+    /// everything right of an entry can't match.
+    fn collect<'a>(
+        &'a self,
+        lo: usize,
+        hi: usize,
+        query: &Query<T, B>,
+        found: &mut Vec<(&'a Interval<T, B>, &'a V)>,
+    ) {
+        if lo >= hi {
+            return;
+        }
+
+        let mid = lo + (hi - lo) / 2;
+        if query.ends_before(self.max_ends[mid]) {
+            return;
+        }
+
+        self.collect(lo, mid, query, found);
+        let (interval, value) = &self.entries[mid];
+        if query.starts_after(interval.start) {
+            return;
+        }
+        if query.matches(interval) {
+            found.push((interval, value));
+        }
+        self.collect(mid + 1, hi, query, found);
+    }
+
+    /// Every interval holding `point`, by start.
+    pub fn stab(&self, point: T) -> Vec<(&Interval<T, B>, &V)> {
+        let mut found = vec![];
+        self.collect(0, self.entries.len(), &Query::Point(point), &mut found);
+        found
+    }
+
+    /// Every interval sharing a value with `range`, by start.
+    pub fn overlapping(&self, range: &Interval<T, B>) -> Vec<(&Interval<T, B>, &V)> {
+        let mut found = vec![];
+        if !range.is_empty() {
+            self.collect(0, self.entries.len(), &Query::Range(*range), &mut found);
+        }
+        found
+    }
+}
+
+enum Query<T, B> {
+    Point(T),
+    Range(Interval<T, B>),
+}
+
+impl<T: Ord + Copy, B: BoundKind> Query<T, B> {
+    /// Whether an interval ending at `end` finishes before the query begins.
+    fn ends_before(&self, end: T) -> bool {
+        match self {
+            Query::Point(point) => B::is_empty(*point, end),
+            Query::Range(range) => B::is_empty(range.start, end),
+        }
+    }
+
+    /// Whether an interval starting at `start` begins after the query is over.
+    fn starts_after(&self, start: T) -> bool {
+        match self {
+            Query::Point(point) => start > *point,
+            Query::Range(range) => B::is_empty(start, range.end),
+        }
+    }
+
+    fn matches(&self, interval: &Interval<T, B>) -> bool {
+        match self {
+            Query::Point(point) => interval.contains_point(*point),
+            Query::Range(range) => interval.overlaps(range),
+        }
+    }
+}
+
+#[cfg(test)]
+mod interval_tests {
+    use crate::interval::*;
+
+    fn closed(start: u32, end: u32) -> Interval<u32> {
+        Interval::new(start, end)
+    }
+
+    fn half_open(start: u32, end: u32) -> Interval<u32, HalfOpen> {
+        Interval::new(start, end)
+    }
+
+    #[test]
+    fn closed_intervals() {
+        let interval = closed(2, 8);
+
+        assert_eq!(interval.len(), 7);
+        assert!(interval.contains(&closed(3, 7)) && !closed(3, 7).contains(&interval));
+        assert!(interval.contains_point(8) && !interval.contains_point(9));
+        assert!(interval.overlaps(&closed(8, 9)) && !interval.overlaps(&closed(9, 10)));
+        assert_eq!(interval.intersection(&closed(5, 12)), closed(5, 8));
+        assert_eq!(interval.union(&closed(9, 12)), Some(closed(2, 12)));
+        assert_eq!(interval.union(&closed(10, 12)), None);
+        assert_eq!(
+            interval.difference(&closed(4, 5)),
+            vec![closed(2, 3), closed(6, 8)]
+        );
+        assert_eq!(interval.difference(&closed(0, 5)), vec![closed(6, 8)]);
+        assert_eq!(interval.difference(&closed(0, 9)), vec![]);
+    }
+
+    #[test]
+    fn half_open_intervals() {
+        let interval = half_open(2, 8);
+
+        assert_eq!(interval.len(), 6);
+        assert!(!interval.contains_point(8));
+        assert!(!interval.overlaps(&half_open(8, 9)));
+        assert_eq!(interval.union(&half_open(8, 12)), Some(half_open(2, 12)));
+        assert_eq!(interval.union(&half_open(9, 12)), None);
+        assert_eq!(
+            interval.difference(&half_open(4, 5)),
+            vec![half_open(2, 4), half_open(5, 8)]
+        );
+        assert_eq!(interval.to_string(), "[2, 8)");
+        assert_eq!(closed(2, 8).to_string(), "2-8");
+    }
+
+    #[test]
+    fn empty_intervals() {
+        assert!(closed(5, 4).is_empty() && !closed(5, 5).is_empty());
+        assert!(half_open(5, 5).is_empty());
+        assert_eq!(closed(5, 4), closed(9, 1));
+        assert_eq!(closed(2, 3).intersection(&closed(5, 6)), closed(1, 0));
+        assert_eq!(closed(5, 4).len(), 0);
+        assert!(closed(2, 3).contains(&closed(9, 1)));
+        assert_eq!(closed(5, 4).union(&closed(2, 3)), Some(closed(2, 3)));
+    }
+
+    #[test]
+    fn extreme_values() {
+        assert_eq!(Interval::<u64>::new(0, u64::MAX).len(), u64::MAX);
+        assert_eq!(Interval::<i64>::new(-2, 2).len(), 5);
+        assert_eq!(
+            Interval::<u8>::new(0, 255).difference(&Interval::new(0, 0)),
+            vec![Interval::new(1, 255)]
+        );
+        assert_eq!(closed(70_000, 80_000).len(), 10_001);
+    }
+
+    #[test]
+    fn interval_sets_merge() {
+        let set: IntervalSet<u32> = [closed(6, 8), closed(2, 4), closed(5, 5), closed(12, 14)]
+            .into_iter()
+            .collect();
+
+        assert_eq!(set.intervals(), [closed(2, 8), closed(12, 14)]);
+        assert_eq!(set.coverage(), 10);
+        assert!(set.covers(8) && set.covers(12) && !set.covers(10) && !set.covers(1));
+
+        let mut inserted = IntervalSet::new();
+        for interval in [closed(12, 14), closed(6, 8), closed(2, 4), closed(5, 5)] {
+            inserted.insert(interval);
+        }
+        assert_eq!(inserted, set);
+        inserted.insert(closed(9, 11));
+        assert_eq!(inserted.intervals(), [closed(2, 14)]);
+    }
+
+    #[test]
+    fn interval_set_gaps() {
+        let set: IntervalSet<u32> = [closed(2, 4), closed(7, 8)].into_iter().collect();
+
+        assert_eq!(
+            set.gaps(closed(1, 10)),
+            vec![closed(1, 1), closed(5, 6), closed(9, 10)]
+        );
+        assert_eq!(set.gaps(closed(3, 7)), vec![closed(5, 6)]);
+        assert_eq!(set.gaps(closed(2, 4)), vec![]);
+        assert_eq!(IntervalSet::new().gaps(closed(2, 4)), vec![closed(2, 4)]);
+
+        let halves: IntervalSet<u32, HalfOpen> =
+            [half_open(2, 4), half_open(4, 6), half_open(8, 9)]
+                .into_iter()
+                .collect();
+        assert_eq!(halves.intervals(), [half_open(2, 6), half_open(8, 9)]);
+        assert_eq!(
+            halves.gaps(half_open(0, 10)),
+            vec![half_open(0, 2), half_open(6, 8), half_open(9, 10)]
+        );
+    }
+
+    /// The quadratic versions, to check the sweeps against.
+    fn naive_pairs<B: BoundKind>(
+        intervals: &[Interval<u32, B>],
+        pair: impl Fn(&Interval<u32, B>, &Interval<u32, B>) -> bool,
+    ) -> Vec<(usize, usize)> {
+        let mut pairs = vec![];
+        for a in 0..intervals.len() {
+            for b in a + 1..intervals.len() {
+                if !intervals[a].is_empty()
+                    && !intervals[b].is_empty()
+                    && pair(&intervals[a], &intervals[b])
+                {
+                    pairs.push((a, b));
+                }
+            }
+        }
+        pairs
+    }
+
+    /// Deterministic pseudo-random intervals, with plenty of repeats and shared end points.
+    fn scattered<B: BoundKind>(count: usize, span: u32) -> Vec<Interval<u32, B>> {
+        let mut state: u64 = 0x2022_0004;
+        let mut next = move || {
+            state = state
+                .wrapping_mul(6364136223846793005)
+                .wrapping_add(1442695040888963407);
+            (state >> 33) as u32 % span
+        };
+
+        (0..count)
+            .map(|_| {
+                let start = next();
+                Interval::new(start, start + next() % 8)
+            })
+            .collect()
+    }
+
+    #[test]
+    fn pairs_match_the_naive_count() {
+        let closed: Vec<Interval<u32>> = scattered(400, 60);
+        let overlapping = naive_pairs(&closed, |a, b| a.overlaps(b));
+        let containing = naive_pairs(&closed, |a, b| a.contains(b) || b.contains(a));
+
+        assert_eq!(count_overlapping_pairs(&closed), overlapping.len());
+        assert_eq!(count_containing_pairs(&closed), containing.len());
+        assert_eq!(overlapping_pairs(&closed), overlapping);
+        assert_eq!(containing_pairs(&closed), containing);
+
+        let halves: Vec<Interval<u32, HalfOpen>> = scattered(400, 60);
+        let overlapping = naive_pairs(&halves, |a, b| a.overlaps(b));
+        let containing = naive_pairs(&halves, |a, b| a.contains(b) || b.contains(a));
+
+        assert_eq!(count_overlapping_pairs(&halves), overlapping.len());
+        assert_eq!(count_containing_pairs(&halves), containing.len());
+        assert_eq!(overlapping_pairs(&halves), overlapping);
+        assert_eq!(containing_pairs(&halves), containing);
+    }
+
+    #[test]
+    fn pairs_of_few_intervals() {
+        let intervals = [
+            closed(2, 4),
+            closed(4, 6),
+            closed(2, 4),
+            closed(9, 8),
+            closed(3, 3),
+        ];
+
+        assert_eq!(
+            overlapping_pairs(&intervals),
+            vec![(0, 1), (0, 2), (0, 4), (1, 2), (2, 4)]
+        );
+        assert_eq!(containing_pairs(&intervals), vec![(0, 2), (0, 4), (2, 4)]);
+        assert_eq!(count_overlapping_pairs::<u32, Closed>(&[]), 0);
+        assert_eq!(count_containing_pairs(&[closed(1, 1)]), 0);
+    }
+
+    #[test]
+    fn counts_many_pairs_quickly() {
+        let intervals: Vec<Interval<u32>> = scattered(300_000, 1_000_000);
+
+        assert!(count_overlapping_pairs(&intervals) > 0);
+        assert!(count_containing_pairs(&intervals) > 0);
+    }
+
+    #[test]
+    fn index_matches_a_scan() {
+        let intervals: Vec<Interval<u32>> = scattered(500, 80);
+        let index = IntervalIndex::new(intervals.iter().copied().zip(0..));
+
+        for point in 0..90 {
+            let expected: Vec<usize> = (0..intervals.len())
+                .filter(|&idx| intervals[idx].contains_point(point))
+                .collect();
+            let mut found: Vec<usize> =
+                index.stab(point).into_iter().map(|(_, &idx)| idx).collect();
+            found.sort();
+            assert_eq!(found, expected, "stabbing {}", point);
+
+            let range = closed(point, point + 3);
+            let expected: Vec<usize> = (0..intervals.len())
+                .filter(|&idx| intervals[idx].overlaps(&range))
+                .collect();
+            let mut found: Vec<usize> = index
+                .overlapping(&range)
+                .into_iter()
+                .map(|(_, &idx)| idx)
+                .collect();
+            found.sort();
+            assert_eq!(found, expected, "overlapping {}", range);
+        }
+    }
+
+    #[test]
+    fn index_respects_bounds() {
+        let index = IntervalIndex::new([
+            (half_open(2, 4), 'a'),
+            (half_open(4, 6), 'b'),
+            (half_open(5, 5), 'c'),
+        ]);
+
+        assert_eq!(index.len(), 2);
+        assert_eq!(index.stab(4), vec![(&half_open(4, 6), &'b')]);
+        assert!(index.stab(6).is_empty());
+        assert_eq!(index.overlapping(&half_open(3, 5)).len(), 2);
+        assert!(index.overlapping(&half_open(4, 4)).is_empty());
+    }
+}
@@ -1 +1,9 @@
+pub mod bench;
 pub mod core;
+pub mod days;
+pub mod examples;
+pub mod interval;
+pub mod scaffold;
+pub mod set_algebra;
+pub mod solution;
+pub mod verify;
@@ -0,0 +1,225 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use crate::core::{AocError, DEFAULT_INPUT};
+use crate::examples::EXAMPLES_FILE;
+use crate::solution::day_name;
+
+const MOD_MARKER: &str = "pub mod day_";
+const REGISTER_MARKER: &str = "registry.register(day_";
+
+fn main_template(day: u8) -> String {
+    format!(
+        "use advent_of_code_2022::days::day_{day}::Day{day};
+use advent_of_code_2022::solution::run_main;
+use std::process::ExitCode;
+
+fn main() -> ExitCode {{
+    run_main(&Day{day})
+}}
+",
+        day = day
+    )
+}
+
+fn solution_template(day: u8) -> String {
+    format!(
+        "use crate::core::AocError;
+use crate::solution::Solution;
+
+pub struct Day{day};
+
+impl Solution for Day{day} {{
+    type Input = Vec<String>;
+    type Answer = usize;
+
+    const DAY: u8 = {day};
+
+    fn parse(&self, input: &str) -> Result<Self::Input, AocError> {{
+        Ok(input.lines().map(String::from).collect())
+    }}
+
+    fn part_one(&self, _input: &Self::Input) -> Result<Self::Answer, AocError> {{
+        Err(AocError::new(\"Part one is not solved yet\"))
+    }}
+
+    fn part_two(&self, _input: &Self::Input) -> Result<Self::Answer, AocError> {{
+        Err(AocError::new(\"Part two is not solved yet\"))
+    }}
+}}
+
+#[cfg(test)]
+mod day_{day}_tests {{
+    use crate::days::day_{day}::*;
+
+    #[test]
+    fn parses_empty_input() {{
+        assert_eq!(Day{day}.parse(\"\"), Ok(vec![]));
+    }}
+}}
+",
+        day = day
+    )
+}
+
+const EXAMPLES_TEMPLATE: &str = "# Puzzle examples, see src/examples.rs for the format:
+# === name
+# --- input
+# ...
+# --- part 1
+# ...
+";
+
+/// Inserts `line` among the lines starting with `marker`, keeping them ordered by the day
+/// number that follows the marker.
+fn insert_registration(
+    source: &str,
+    marker: &str,
+    day: u8,
+    line: &str,
+) -> Result<String, AocError> {
+    let mut lines: Vec<String> = source.lines().map(String::from).collect();
+    let registered: Vec<(usize, u8)> = lines
+        .iter()
+        .enumerate()
+        .filter_map(|(idx, existing)| {
+            let rest = existing.trim_start().strip_prefix(marker)?;
+            let digits: String = rest.chars().take_while(char::is_ascii_digit).collect();
+            Some((idx, digits.parse().ok()?))
+        })
+        .collect();
+
+    if registered.iter().any(|(_, existing)| *existing == day) {
+        return Err(AocError::new(format!("Day {} is already registered", day)));
+    }
+
+    let (last_idx, _) = registered.last().ok_or_else(|| {
+        AocError::new(format!(
+            "Couldn't find any `{}` lines to register next to",
+            marker
+        ))
+    })?;
+    let indent: String = lines[*last_idx]
+        .chars()
+        .take_while(|c| c.is_whitespace())
+        .collect();
+    let position = registered
+        .iter()
+        .find(|(_, existing)| *existing > day)
+        .map_or(last_idx + 1, |(idx, _)| *idx);
+
+    lines.insert(position, format!("{}{}", indent, line));
+    Ok(lines.join("\n") + "\n")
+}
+
+/// Creates everything a new day needs under the crate at `root` and registers it, returning
+/// the files it wrote. Nothing is written if any of them already exists.
+pub fn scaffold_day(root: &Path, day: u8) -> Result<Vec<PathBuf>, AocError> {
+    if !(1..=25).contains(&day) {
+        return Err(AocError::new(format!("Day {} is not in 1..=25", day)));
+    }
+
+    let day_dir = root.join("src/bin").join(day_name(day));
+    let files = vec![
+        (day_dir.join("main.rs"), main_template(day)),
+        (
+            day_dir.join(format!("{}.txt", DEFAULT_INPUT)),
+            String::new(),
+        ),
+        (
+            day_dir.join(format!("{}.txt", EXAMPLES_FILE)),
+            EXAMPLES_TEMPLATE.to_string(),
+        ),
+        (
+            root.join("src/days").join(format!("day_{}.rs", day)),
+            solution_template(day),
+        ),
+    ];
+
+    if let Some((existing, _)) = files.iter().find(|(path, _)| path.exists()) {
+        return Err(AocError::new(format!(
+            "Refusing to overwrite {}",
+            existing.display()
+        )));
+    }
+
+    let registry_path = root.join("src/days/mod.rs");
+    let registry = fs::read_to_string(&registry_path)?;
+    let registry =
+        insert_registration(&registry, MOD_MARKER, day, &format!("pub mod day_{};", day))?;
+    let registry = insert_registration(
+        &registry,
+        REGISTER_MARKER,
+        day,
+        &format!("registry.register(day_{day}::Day{day});", day = day),
+    )?;
+
+    fs::create_dir_all(&day_dir)?;
+    for (path, contents) in &files {
+        fs::write(path, contents)?;
+    }
+    fs::write(&registry_path, registry)?;
+
+    Ok(files
+        .into_iter()
+        .map(|(path, _)| path)
+        .chain([registry_path])
+        .collect())
+}
+
+#[cfg(test)]
+mod scaffold_tests {
+    use crate::scaffold::*;
+
+    const REGISTRY: &str = "use crate::solution::Registry;
+
+pub mod day_3;
+pub mod day_6;
+
+pub fn registry() -> Registry {
+    let mut registry = Registry::default();
+    registry.register(day_3::Day3);
+    registry.register(day_6::Day6);
+
+    registry
+}
+";
+
+    #[test]
+    fn registrations_stay_ordered() {
+        let registry = insert_registration(REGISTRY, MOD_MARKER, 4, "pub mod day_4;")
+            .and_then(|source| {
+                insert_registration(
+                    &source,
+                    REGISTER_MARKER,
+                    7,
+                    "registry.register(day_7::Day7);",
+                )
+            })
+            .expect("Could not register");
+
+        assert!(registry.contains("pub mod day_3;\npub mod day_4;\npub mod day_6;\n"));
+        assert!(registry.contains(
+            "    registry.register(day_6::Day6);\n    registry.register(day_7::Day7);\n"
+        ));
+        assert!(insert_registration(REGISTRY, MOD_MARKER, 3, "pub mod day_3;").is_err());
+    }
+
+    #[test]
+    fn scaffold_refuses_to_overwrite() {
+        let root = std::env::temp_dir().join(format!("aoc-scaffold-{}", std::process::id()));
+        fs::create_dir_all(root.join("src/days")).expect("Could not create crate");
+        fs::write(root.join("src/days/mod.rs"), REGISTRY).expect("Could not write registry");
+
+        let written = scaffold_day(&root, 9).expect("Could not scaffold");
+        let registry = fs::read_to_string(root.join("src/days/mod.rs")).expect("");
+        let again = scaffold_day(&root, 9);
+        fs::remove_dir_all(&root).expect("Could not clean up");
+
+        assert_eq!(written.len(), 5);
+        assert!(written.contains(&root.join("src/bin/day-9/examples.txt")));
+        assert!(registry.contains("pub mod day_9;"));
+        assert!(registry.contains("registry.register(day_9::Day9);"));
+        assert!(again.is_err());
+    }
+}
@@ -0,0 +1,153 @@
+//! N-ary set operations over anything implementing [`SetAlgebra`].
+//!
+//! The n-ary forms take any iterator of sets, owned or borrowed. Union and symmetric
+//! difference of no sets is the empty set. Intersection and difference of no sets have no
+//! sensible answer (the intersection would be every possible item), so they return `None`.
+
+use std::borrow::Borrow;
+use std::collections::HashSet;
+use std::hash::{BuildHasher, Hash};
+
+pub trait SetAlgebra: Clone {
+    fn empty() -> Self;
+
+    fn intersect_with(&mut self, other: &Self);
+
+    fn union_with(&mut self, other: &Self);
+
+    fn difference_with(&mut self, other: &Self);
+
+    fn symmetric_difference_with(&mut self, other: &Self);
+
+    /// Items in every set, or `None` for no sets.
+    fn intersection_all<I, B>(sets: I) -> Option<Self>
+    where
+        I: IntoIterator<Item = B>,
+        B: Borrow<Self>,
+    {
+        let mut sets = sets.into_iter();
+        let mut result = sets.next()?.borrow().clone();
+        sets.for_each(|set| result.intersect_with(set.borrow()));
+
+        Some(result)
+    }
+
+    /// Items in any set.
+    fn union_all<I, B>(sets: I) -> Self
+    where
+        I: IntoIterator<Item = B>,
+        B: Borrow<Self>,
+    {
+        sets.into_iter().fold(Self::empty(), |mut result, set| {
+            result.union_with(set.borrow());
+            result
+        })
+    }
+
+    /// Items in the first set and none of the others, or `None` for no sets.
+    fn difference_all<I, B>(sets: I) -> Option<Self>
+    where
+        I: IntoIterator<Item = B>,
+        B: Borrow<Self>,
+    {
+        let mut sets = sets.into_iter();
+        let mut result = sets.next()?.borrow().clone();
+        sets.for_each(|set| result.difference_with(set.borrow()));
+
+        Some(result)
+    }
+
+    /// Items in an odd number of the sets, which is what folding the pairwise symmetric
+    /// difference gives.
+    fn symmetric_difference_all<I, B>(sets: I) -> Self
+    where
+        I: IntoIterator<Item = B>,
+        B: Borrow<Self>,
+    {
+        sets.into_iter().fold(Self::empty(), |mut result, set| {
+            result.symmetric_difference_with(set.borrow());
+            result
+        })
+    }
+}
+
+impl<T, S> SetAlgebra for HashSet<T, S>
+where
+    T: Hash + Eq + Clone,
+    S: BuildHasher + Default + Clone,
+{
+    fn empty() -> Self {
+        HashSet::default()
+    }
+
+    fn intersect_with(&mut self, other: &Self) {
+        self.retain(|item| other.contains(item));
+    }
+
+    fn union_with(&mut self, other: &Self) {
+        self.extend(other.iter().cloned());
+    }
+
+    fn difference_with(&mut self, other: &Self) {
+        self.retain(|item| !other.contains(item));
+    }
+
+    fn symmetric_difference_with(&mut self, other: &Self) {
+        for item in other {
+            if !self.remove(item) {
+                self.insert(item.clone());
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod set_algebra_tests {
+    use crate::set_algebra::*;
+
+    fn set(items: &str) -> HashSet<char> {
+        items.chars().collect()
+    }
+
+    fn sorted(set: &HashSet<char>) -> String {
+        let mut items: Vec<char> = set.iter().copied().collect();
+        items.sort();
+        items.into_iter().collect()
+    }
+
+    #[test]
+    fn n_ary_operations() {
+        let sets = vec![set("abcd"), set("bcde"), set("cdef")];
+
+        assert_eq!(
+            HashSet::intersection_all(&sets).map(|s| sorted(&s)),
+            Some("cd".into())
+        );
+        assert_eq!(sorted(&HashSet::union_all(&sets)), "abcdef");
+        assert_eq!(
+            HashSet::difference_all(&sets).map(|s| sorted(&s)),
+            Some("a".into())
+        );
+        assert_eq!(sorted(&HashSet::symmetric_difference_all(&sets)), "acdf");
+    }
+
+    #[test]
+    fn takes_owned_sets() {
+        let lines = ["abc", "bcd"];
+
+        assert_eq!(
+            HashSet::intersection_all(lines.iter().map(|line| set(line))).map(|s| sorted(&s)),
+            Some("bc".into())
+        );
+    }
+
+    #[test]
+    fn empty_input() {
+        let sets: Vec<HashSet<char>> = vec![];
+
+        assert_eq!(HashSet::intersection_all(&sets), None);
+        assert_eq!(HashSet::difference_all(&sets), None);
+        assert!(HashSet::union_all(&sets).is_empty());
+        assert!(HashSet::symmetric_difference_all(&sets).is_empty());
+    }
+}
@@ -0,0 +1,235 @@
+use std::any::Any;
+use std::collections::BTreeMap;
+use std::fmt::{Display, Formatter};
+use std::io::BufRead;
+use std::process::ExitCode;
+use std::str::FromStr;
+
+use crate::core::{get_data, AocError};
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Hash, Ord, PartialOrd)]
+pub enum Part {
+    One,
+    Two,
+}
+
+impl Part {
+    pub const ALL: [Part; 2] = [Part::One, Part::Two];
+}
+
+impl FromStr for Part {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "1" | "one" => Ok(Part::One),
+            "2" | "two" => Ok(Part::Two),
+            _ => Err(format!("Unknown part {}, expected 1 or 2", s)),
+        }
+    }
+}
+
+impl Display for Part {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Part::One => f.pad("1"),
+            Part::Two => f.pad("2"),
+        }
+    }
+}
+
+/// The input directory name for a day, e.g. `day-4`.
+pub fn day_name(day: u8) -> String {
+    format!("day-{}", day)
+}
+
+/// A single day of the calendar: one parser feeding both parts.
+pub trait Solution {
+    type Input;
+    type Answer: Display;
+
+    const DAY: u8;
+
+    fn parse(&self, input: &str) -> Result<Self::Input, AocError>;
+
+    /// Parses straight from a reader. Days that can build their input line by line
+    /// override this so the raw text never has to be held in memory.
+    fn parse_reader(&self, reader: &mut dyn BufRead) -> Result<Self::Input, AocError> {
+        let mut input = String::new();
+        reader.read_to_string(&mut input)?;
+
+        self.parse(&input)
+    }
+
+    fn part_one(&self, input: &Self::Input) -> Result<Self::Answer, AocError>;
+
+    fn part_two(&self, input: &Self::Input) -> Result<Self::Answer, AocError>;
+
+    fn solve_part(&self, input: &Self::Input, part: Part) -> Result<Self::Answer, AocError> {
+        match part {
+            Part::One => self.part_one(input),
+            Part::Two => self.part_two(input),
+        }
+    }
+
+    /// Sets a day-specific option, as given by `--opt key=value` on the runner. Days without
+    /// options reject every key.
+    fn configure(&mut self, key: &str, _value: &str) -> Result<(), AocError> {
+        Err(AocError::new(format!(
+            "Day {} has no option {:?}",
+            Self::DAY,
+            key
+        )))
+    }
+}
+
+/// The parsed input of some day, handed back to the same day's parts.
+pub struct Parsed(Box<dyn Any>);
+
+/// Object-safe view of a [`Solution`], so days with different input and answer types can
+/// sit in the same [`Registry`].
+pub trait DynSolution {
+    fn day(&self) -> u8;
+
+    fn parse(&self, input: &str) -> Result<Parsed, AocError>;
+
+    fn parse_reader(&self, reader: &mut dyn BufRead) -> Result<Parsed, AocError>;
+
+    fn run(&self, parsed: &Parsed, part: Part) -> Result<String, AocError>;
+
+    fn configure(&mut self, key: &str, value: &str) -> Result<(), AocError>;
+
+    fn solve(&self, input: &str, part: Part) -> Result<String, AocError> {
+        self.run(&self.parse(input)?, part)
+    }
+}
+
+impl<S> DynSolution for S
+where
+    S: Solution,
+    S::Input: 'static,
+{
+    fn day(&self) -> u8 {
+        S::DAY
+    }
+
+    fn parse(&self, input: &str) -> Result<Parsed, AocError> {
+        Ok(Parsed(Box::new(Solution::parse(self, input)?)))
+    }
+
+    fn parse_reader(&self, reader: &mut dyn BufRead) -> Result<Parsed, AocError> {
+        Ok(Parsed(Box::new(Solution::parse_reader(self, reader)?)))
+    }
+
+    fn run(&self, parsed: &Parsed, part: Part) -> Result<String, AocError> {
+        let input = parsed.0.downcast_ref::<S::Input>().ok_or_else(|| {
+            AocError::new(format!("Parsed input was not produced by day {}", S::DAY))
+        })?;
+
+        Ok(self.solve_part(input, part)?.to_string())
+    }
+
+    fn configure(&mut self, key: &str, value: &str) -> Result<(), AocError> {
+        Solution::configure(self, key, value)
+    }
+}
+
+#[derive(Default)]
+pub struct Registry {
+    solutions: BTreeMap<u8, Box<dyn DynSolution>>,
+}
+
+impl Registry {
+    pub fn register<S>(&mut self, solution: S)
+    where
+        S: Solution + 'static,
+        S::Input: 'static,
+    {
+        self.solutions.insert(S::DAY, Box::new(solution));
+    }
+
+    pub fn get(&self, day: u8) -> Option<&dyn DynSolution> {
+        self.solutions.get(&day).map(|solution| solution.as_ref())
+    }
+
+    pub fn get_mut(&mut self, day: u8) -> Option<&mut dyn DynSolution> {
+        self.solutions
+            .get_mut(&day)
+            .map(|solution| solution.as_mut() as &mut dyn DynSolution)
+    }
+
+    pub fn days(&self) -> impl Iterator<Item = u8> + '_ {
+        self.solutions.keys().copied()
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &dyn DynSolution> {
+        self.solutions.values().map(|solution| solution.as_ref())
+    }
+}
+
+/// Reads a day's default input and prints both parts; the body of every `bin/day-N` binary.
+pub fn run_main(solution: &dyn DynSolution) -> ExitCode {
+    let parsed = match get_data(&day_name(solution.day()))
+        .map_err(AocError::from)
+        .and_then(|data| solution.parse(&data))
+    {
+        Ok(parsed) => parsed,
+        Err(err) => {
+            eprintln!("{}", err);
+            return ExitCode::FAILURE;
+        }
+    };
+
+    let mut exit_code = ExitCode::SUCCESS;
+    for part in Part::ALL {
+        match solution.run(&parsed, part) {
+            Ok(answer) => println!("Part {}: {}", part, answer),
+            Err(err) => {
+                eprintln!("Part {}: {}", part, err);
+                exit_code = ExitCode::FAILURE;
+            }
+        }
+    }
+
+    exit_code
+}
+
+#[cfg(test)]
+mod solution_tests {
+    use crate::days::registry;
+    use crate::solution::*;
+
+    #[test]
+    fn registry_is_ordered_by_day() {
+        let days: Vec<u8> = registry().days().collect();
+
+        assert!(days.windows(2).all(|pair| pair[0] < pair[1]));
+        assert!((1..=6).all(|day| days.contains(&day)));
+    }
+
+    #[test]
+    fn parsed_input_is_tied_to_its_day() {
+        let registry = registry();
+        let day_4 = registry.get(4).expect("Day 4 is registered");
+        let day_6 = registry.get(6).expect("Day 6 is registered");
+        let parsed = day_6.parse("abcde").expect("Could not parse");
+
+        assert!(day_4.run(&parsed, Part::One).is_err());
+        assert_eq!(day_6.run(&parsed, Part::One), Ok("4".to_string()));
+    }
+
+    #[test]
+    fn options_reach_the_registered_day() {
+        let mut registry = registry();
+        let day_3 = registry.get_mut(3).expect("Day 3 is registered");
+        day_3
+            .configure("group-size", "2")
+            .expect("Could not configure");
+
+        let parsed = day_3.parse("abab\nbcbc").expect("Could not parse");
+        assert_eq!(day_3.run(&parsed, Part::Two), Ok("2".to_string()));
+
+        let day_6 = registry.get_mut(6).expect("Day 6 is registered");
+        assert!(day_6.configure("group-size", "2").is_err());
+    }
+}
@@ -0,0 +1,219 @@
+use std::collections::BTreeMap;
+use std::fmt::{Display, Formatter};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use crate::core::{AocError, InputSource, DEFAULT_INPUT};
+use crate::solution::{day_name, Part, Registry};
+
+/// The checked-in answer key, next to `Cargo.toml`.
+pub fn default_answers_path() -> PathBuf {
+    Path::new(env!("CARGO_MANIFEST_DIR")).join("answers.txt")
+}
+
+/// Known answers keyed by day, part and input name. The file holds one
+/// `day part input answer` line per answer; the answer runs to the end of the line.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct AnswerKey {
+    answers: BTreeMap<(u8, Part, String), String>,
+}
+
+impl AnswerKey {
+    pub fn parse(input: &str) -> Result<Self, AocError> {
+        let mut answers = BTreeMap::new();
+
+        for (idx, line) in input.lines().enumerate() {
+            if line.trim().is_empty() || line.trim_start().starts_with('#') {
+                continue;
+            }
+
+            let invalid = |message: &str| AocError::at(message, line, 1, line).on_line(idx + 1);
+            let mut fields = line.trim().splitn(4, char::is_whitespace);
+            let (day, part, name, answer) =
+                match (fields.next(), fields.next(), fields.next(), fields.next()) {
+                    (Some(day), Some(part), Some(name), Some(answer)) => (day, part, name, answer),
+                    _ => return Err(invalid("Expected `day part input answer`")),
+                };
+
+            let key = (
+                day.parse().map_err(|_| invalid("Expected a day number"))?,
+                part.parse().map_err(|_| invalid("Expected part 1 or 2"))?,
+                name.to_string(),
+            );
+            if answers.insert(key, answer.trim().to_string()).is_some() {
+                return Err(invalid("Duplicate answer"));
+            }
+        }
+
+        Ok(Self { answers })
+    }
+
+    pub fn load(path: &Path) -> Result<Self, AocError> {
+        AnswerKey::parse(&fs::read_to_string(path).map_err(|err| {
+            AocError::new(format!("Couldn't read answers {}: {}", path.display(), err))
+        })?)
+    }
+
+    pub fn get(&self, day: u8, part: Part, input: &str) -> Option<&str> {
+        self.answers
+            .get(&(day, part, input.to_string()))
+            .map(String::as_str)
+    }
+
+    /// Every input name the key mentions for a day, always including the day's default input.
+    pub fn inputs(&self, day: u8) -> Vec<String> {
+        let mut inputs: Vec<String> = self
+            .answers
+            .keys()
+            .filter(|(answer_day, _, _)| *answer_day == day)
+            .map(|(_, _, name)| name.clone())
+            .chain([DEFAULT_INPUT.to_string()])
+            .collect();
+        inputs.sort();
+        inputs.dedup();
+
+        inputs
+    }
+}
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum Status {
+    Pass,
+    Fail,
+    /// There is no known answer to compare against.
+    Missing,
+    /// The input couldn't be read or the solver failed.
+    Error,
+}
+
+impl Display for Status {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Status::Pass => f.pad("pass"),
+            Status::Fail => f.pad("FAIL"),
+            Status::Missing => f.pad("missing"),
+            Status::Error => f.pad("ERROR"),
+        }
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct Check {
+    pub day: u8,
+    pub part: Part,
+    pub input: String,
+    pub expected: Option<String>,
+    pub actual: Result<String, AocError>,
+}
+
+impl Check {
+    pub fn status(&self) -> Status {
+        match (&self.expected, &self.actual) {
+            (None, _) => Status::Missing,
+            (Some(_), Err(_)) => Status::Error,
+            (Some(expected), Ok(actual)) if expected == actual => Status::Pass,
+            (Some(_), Ok(_)) => Status::Fail,
+        }
+    }
+}
+
+/// Runs every registered day against each of its inputs the key knows about, reading them
+/// with `read_input(day, input name)`.
+pub fn verify<F>(registry: &Registry, key: &AnswerKey, read_input: F) -> Vec<Check>
+where
+    F: Fn(u8, &str) -> Result<String, AocError>,
+{
+    let mut checks = vec![];
+
+    for solution in registry.iter() {
+        let day = solution.day();
+        for input in key.inputs(day) {
+            let parsed = read_input(day, &input).and_then(|data| solution.parse(&data));
+
+            for part in Part::ALL {
+                checks.push(Check {
+                    day,
+                    part,
+                    input: input.clone(),
+                    expected: key.get(day, part, &input).map(String::from),
+                    actual: parsed
+                        .as_ref()
+                        .map_err(Clone::clone)
+                        .and_then(|parsed| solution.run(parsed, part)),
+                });
+            }
+        }
+    }
+
+    checks
+}
+
+/// Reads inputs the same way the runner does, honouring `AOC_INPUT_ROOT`.
+pub fn read_day_input(day: u8, input: &str) -> Result<String, AocError> {
+    Ok(InputSource::for_input(&day_name(day), input).read_to_string()?)
+}
+
+#[cfg(test)]
+mod verify_tests {
+    use crate::days::registry;
+    use crate::verify::*;
+
+    #[test]
+    fn parse_answer_key() {
+        let key = AnswerKey::parse("# day part input answer\n4 1 input 464\n5 2 example CMZ\n")
+            .expect("Could not parse answers");
+
+        assert_eq!(key.get(4, Part::One, "input"), Some("464"));
+        assert_eq!(key.get(5, Part::Two, "example"), Some("CMZ"));
+        assert_eq!(key.get(4, Part::Two, "input"), None);
+        assert_eq!(key.inputs(5), vec!["example", "input"]);
+    }
+
+    #[test]
+    fn duplicate_answers_are_rejected() {
+        let err = AnswerKey::parse("4 1 input 464\n4 1 input 465").expect_err("Expected an error");
+
+        assert_eq!(err.location.map(|location| location.line), Some(2));
+    }
+
+    #[test]
+    fn statuses() {
+        let key = AnswerKey::parse("6 1 input 7\n6 2 input 20").expect("Could not parse answers");
+        let checks = verify(&registry(), &key, |day, _| match day {
+            6 => Ok("mjqjpqmgbljsphdztnvjfqwrcgsmlb".to_string()),
+            _ => Err(AocError::new("No input")),
+        });
+        let statuses: Vec<(u8, Part, Status)> = checks
+            .iter()
+            .filter(|check| check.day == 6 || check.day == 4)
+            .map(|check| (check.day, check.part, check.status()))
+            .collect();
+
+        assert_eq!(
+            statuses,
+            vec![
+                (4, Part::One, Status::Missing),
+                (4, Part::Two, Status::Missing),
+                (6, Part::One, Status::Pass),
+                (6, Part::Two, Status::Fail),
+            ]
+        );
+    }
+
+    #[test]
+    fn known_answers() {
+        let key = AnswerKey::load(&default_answers_path()).expect("Could not load answers");
+        let failures: Vec<String> = verify(&registry(), &key, read_day_input)
+            .into_iter()
+            .filter(|check| matches!(check.status(), Status::Fail | Status::Error))
+            .map(|check| {
+                format!(
+                    "day {} part {} {}: {:?}",
+                    check.day, check.part, check.input, check.actual
+                )
+            })
+            .collect();
+
+        assert!(failures.is_empty(), "{:#?}", failures);
+    }
+}